@@ -0,0 +1,308 @@
+use std::{marker::PhantomData, ops::Add};
+
+use crate::{stack::Stack, traits::{Buffer, Container, LifoBuffer}};
+
+// ------------------------------ Min Max Stack ------------------------------
+// Next to the values sit two stacks holding the depths of the current minimum and maximum.
+// A new extremum is pushed only when a value ties or beats the old one and popped again
+// together with that value, so min and max stay O(1) without cloning anything
+pub struct MinMaxStack<T> {
+    values: Vec<T>,
+    mins: Stack<usize>,
+    maxs: Stack<usize>
+}
+
+impl <T: Ord> Default for MinMaxStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T: Ord> MinMaxStack<T> {
+    pub fn new() -> Self {
+        MinMaxStack { values: Vec::new(), mins: Stack::new(), maxs: Stack::new() }
+    }
+
+    pub fn push(&mut self, val: T) {
+        let depth = self.values.len();
+
+        if self.min().is_none_or(|min| val <= *min) {
+            self.mins.push(depth);
+        }
+
+        if self.max().is_none_or(|max| val >= *max) {
+            self.maxs.push(depth);
+        }
+
+        self.values.push(val);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let val = self.values.pop()?;
+        let depth = self.values.len();
+
+        if self.mins.peek() == Some(&depth) {
+            self.mins.pop();
+        }
+
+        if self.maxs.peek() == Some(&depth) {
+            self.maxs.pop();
+        }
+
+        Some(val)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.values.last()
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.mins.peek().map(|&depth| &self.values[depth])
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.maxs.peek().map(|&depth| &self.values[depth])
+    }
+
+    // From the top of the stack down, like Stack::iter
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter().rev()
+    }
+}
+
+impl <T: Ord> Container for MinMaxStack<T> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn clear(&mut self) {
+        self.mins.clear();
+        self.maxs.clear();
+        self.values.clear();
+    }
+}
+
+impl <T: Ord> Buffer<T> for MinMaxStack<T> {
+    fn push(&mut self, val: T) {
+        MinMaxStack::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MinMaxStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        MinMaxStack::peek(self)
+    }
+}
+
+impl <T: Ord> LifoBuffer<T> for MinMaxStack<T> {}
+
+// ------------------------------ Monoid ------------------------------
+// Associative combine with an identity value, folding in any grouping gives the same result
+pub trait Monoid<T> {
+    fn identity() -> T;
+    fn combine(a: &T, b: &T) -> T;
+}
+
+pub struct Sum;
+
+impl <T: Copy + Default + Add<Output = T>> Monoid<T> for Sum {
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn combine(a: &T, b: &T) -> T {
+        *a + *b
+    }
+}
+
+// Greatest common divisor, 0 divides nothing so it's the identity
+pub struct Gcd;
+
+impl Monoid<u64> for Gcd {
+    fn identity() -> u64 {
+        0
+    }
+
+    fn combine(a: &u64, b: &u64) -> u64 {
+        let (mut a, mut b) = (*a, *b);
+
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        a
+    }
+}
+
+// ------------------------------ Aggregating Stack ------------------------------
+// Every entry keeps the fold of itself and everything below it, so the fold of the whole
+// stack is always the top entry's
+pub struct AggregatingStack<T, M> {
+    entries: Stack<(T, T)>,
+    phantom: PhantomData<M>
+}
+
+impl <T, M: Monoid<T>> Default for AggregatingStack<T, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T, M: Monoid<T>> AggregatingStack<T, M> {
+    pub fn new() -> Self {
+        AggregatingStack { entries: Stack::new(), phantom: PhantomData }
+    }
+
+    pub fn push(&mut self, val: T) {
+        let acc = match self.entries.peek() {
+            Some((_, acc)) => M::combine(acc, &val),
+            None => M::combine(&M::identity(), &val)
+        };
+
+        self.entries.push((val, acc));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.entries.pop().map(|(val, _)| val)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.entries.peek().map(|(val, _)| val)
+    }
+
+    // Fold of every value on the stack, the identity when it's empty
+    pub fn aggregate(&self) -> T where T: Clone {
+        self.entries.peek().map_or_else(M::identity, |(_, acc)| acc.clone())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().map(|(val, _)| val)
+    }
+}
+
+impl <T, M: Monoid<T>> Container for AggregatingStack<T, M> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl <T, M: Monoid<T>> Buffer<T> for AggregatingStack<T, M> {
+    fn push(&mut self, val: T) {
+        AggregatingStack::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        AggregatingStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        AggregatingStack::peek(self)
+    }
+}
+
+impl <T, M: Monoid<T>> LifoBuffer<T> for AggregatingStack<T, M> {}
+
+#[test]
+fn min_max() {
+    let mut stack = MinMaxStack::new();
+
+    assert_eq!(stack.min(), None);
+
+    for val in [5, 3, 8, 3, 9, 1] {
+        stack.push(val);
+    }
+
+    assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&9)));
+    assert_eq!(stack.pop(), Some(1));
+    assert_eq!((stack.min(), stack.max()), (Some(&3), Some(&9)));
+    stack.pop();
+    assert_eq!(stack.max(), Some(&8));
+
+    // The duplicate 3 keeps the minimum after the upper one goes
+    stack.pop();
+    assert_eq!(stack.min(), Some(&3));
+    stack.pop();
+    assert_eq!((stack.min(), stack.max()), (Some(&3), Some(&5)));
+    assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![3, 5]);
+
+    stack.clear();
+    assert_eq!(stack.pop(), None);
+    assert_eq!((stack.min(), stack.max()), (None, None));
+}
+
+#[test]
+fn min_max_without_clone() {
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct Token(String);
+
+    let mut stack = MinMaxStack::new();
+    stack.push(Token("m".to_string()));
+    stack.push(Token("a".to_string()));
+    stack.push(Token("z".to_string()));
+
+    assert_eq!(stack.min(), Some(&Token("a".to_string())));
+    assert_eq!(stack.max(), Some(&Token("z".to_string())));
+    assert_eq!(stack.len(), 3);
+}
+
+#[test]
+fn aggregating_sum_gcd() {
+    let mut sums = AggregatingStack::<i64, Sum>::new();
+
+    assert_eq!(sums.aggregate(), 0);
+    sums.push(4);
+    sums.push(-1);
+    sums.push(10);
+    assert_eq!(sums.aggregate(), 13);
+    assert_eq!(sums.pop(), Some(10));
+    assert_eq!(sums.aggregate(), 3);
+
+    let mut gcds = AggregatingStack::<u64, Gcd>::new();
+
+    for val in [48, 36, 90] {
+        gcds.push(val);
+    }
+
+    assert_eq!(gcds.aggregate(), 6);
+    gcds.push(7);
+    assert_eq!(gcds.aggregate(), 1);
+    gcds.pop();
+    gcds.pop();
+    assert_eq!(gcds.aggregate(), 12);
+}
+
+// Two aggregating stacks make a queue whose fold is still O(1) amortised, the usual way to
+// aggregate a sliding window
+#[test]
+fn sliding_window_gcd() {
+    let values = [12, 18, 24, 7, 14, 28, 35];
+    let mut front = AggregatingStack::<u64, Gcd>::new();
+    let mut back = AggregatingStack::<u64, Gcd>::new();
+    let mut gcds = Vec::new();
+
+    for (i, &val) in values.iter().enumerate() {
+        back.push(val);
+
+        if i >= 3 {
+            if front.is_empty() {
+                while let Some(val) = back.pop() {
+                    front.push(val);
+                }
+            }
+
+            front.pop();
+        }
+
+        if i >= 2 {
+            gcds.push(Gcd::combine(&front.aggregate(), &back.aggregate()));
+        }
+    }
+
+    assert_eq!(gcds, vec![6, 1, 1, 7, 7]);
+}
@@ -0,0 +1,295 @@
+use std::{fmt::{self, Debug}, marker::PhantomData};
+
+use crate::{linked_list::{LinkedList, Node, NodePointer}, traits::Container};
+
+// ------------------------------ Circular List ------------------------------
+// Doubly linked ring of the same nodes LinkedList uses, the tail's next is the head and
+// the head's prev is the tail. There is no fixed start, only a movable current node
+pub struct CircularList<T> {
+    current: Option<NodePointer<T>>,
+    length: usize,
+    phantom: PhantomData<Box<Node<T>>>
+}
+
+impl <T> Default for CircularList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> CircularList<T> {
+    pub fn new() -> Self {
+        CircularList { current: None, length: 0, phantom: PhantomData }
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.current.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    // Moves current k nodes forward, wrapping around the ring
+    pub fn advance(&mut self, k: usize) {
+        let Some(mut node) = self.current else { return };
+
+        for _ in 0..k % self.length {
+            node = unsafe { next_node(node) };
+        }
+
+        self.current = Some(node);
+    }
+
+    // Moves current k nodes backward, wrapping around the ring
+    pub fn retreat(&mut self, k: usize) {
+        let Some(mut node) = self.current else { return };
+
+        for _ in 0..k % self.length {
+            node = unsafe { prev_node(node) };
+        }
+
+        self.current = Some(node);
+    }
+
+    // Inserts right before current, which is the end of the ring when counting from current
+    pub fn push(&mut self, val: T) {
+        match self.current {
+            Some(node) => unsafe { self.link_after(prev_node(node), val) },
+            None => self.link_first(val)
+        }
+    }
+
+    // Inserts right after current without moving it, the first value becomes current
+    pub fn insert_after_current(&mut self, val: T) {
+        match self.current {
+            Some(node) => unsafe { self.link_after(node, val) },
+            None => self.link_first(val)
+        }
+    }
+
+    // Removes current and moves on to the node after it
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        self.length -= 1;
+
+        if self.length == 0 {
+            self.current = None;
+        } else {
+            let (prev, next) = (node.prev.expect("ring is closed"), node.next.expect("ring is closed"));
+
+            unsafe {
+                (*prev.as_ptr()).next = Some(next);
+                (*next.as_ptr()).prev = Some(prev);
+            }
+
+            self.current = Some(next);
+        }
+
+        Some(node.val)
+    }
+
+    // Goes around the ring once starting at current
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { current: self.current, remaining: self.length, phantom: PhantomData }
+    }
+
+    fn link_first(&mut self, val: T) {
+        let node = Node::alloc(val);
+
+        unsafe {
+            (*node.as_ptr()).prev = Some(node);
+            (*node.as_ptr()).next = Some(node);
+        }
+
+        self.current = Some(node);
+        self.length = 1;
+    }
+
+    unsafe fn link_after(&mut self, prev: NodePointer<T>, val: T) {
+        let node = Node::alloc(val);
+        let next = next_node(prev);
+
+        (*node.as_ptr()).prev = Some(prev);
+        (*node.as_ptr()).next = Some(next);
+        (*prev.as_ptr()).next = Some(node);
+        (*next.as_ptr()).prev = Some(node);
+
+        self.length += 1;
+    }
+}
+
+unsafe fn next_node<T>(node: NodePointer<T>) -> NodePointer<T> {
+    (*node.as_ptr()).next.expect("ring is closed")
+}
+
+unsafe fn prev_node<T>(node: NodePointer<T>) -> NodePointer<T> {
+    (*node.as_ptr()).prev.expect("ring is closed")
+}
+
+// Closes the list into a ring in O(1), the old head becomes current
+impl <T> From<LinkedList<T>> for CircularList<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        let (head, tail, length) = list.into_nodes();
+
+        if let (Some(head), Some(tail)) = (head, tail) {
+            unsafe {
+                (*head.as_ptr()).prev = Some(tail);
+                (*tail.as_ptr()).next = Some(head);
+            }
+        }
+
+        CircularList { current: head, length, phantom: PhantomData }
+    }
+}
+
+impl <T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        CircularList::from(iter.into_iter().collect::<LinkedList<T>>())
+    }
+}
+
+impl <T> Drop for CircularList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl <T> Container for CircularList<T> {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        while self.remove_current().is_some() {}
+    }
+}
+
+impl <T: Debug> Debug for CircularList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// ------------------------------ Iterator ------------------------------
+pub struct Iter<'a, T> {
+    current: Option<NodePointer<T>>,
+    remaining: usize,
+    phantom: PhantomData<&'a T>
+}
+
+impl <'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.current.map(|node| unsafe {
+            self.remaining -= 1;
+            self.current = (*node.as_ptr()).next;
+            &(*node.as_ptr()).val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> ExactSizeIterator for Iter<'_, T> {}
+
+// ------------------------------ Application ------------------------------
+// People 1..=n stand in a circle and every k-th one is eliminated, counting on from the
+// next person. Returns the positions in the order they are eliminated
+pub fn josephus_order(n: usize, k: usize) -> Vec<usize> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut circle: CircularList<usize> = (1..=n).collect();
+    let mut order = Vec::with_capacity(n);
+
+    while !circle.is_empty() {
+        circle.advance(k - 1);
+        order.extend(circle.remove_current());
+    }
+
+    order
+}
+
+// Position of the last person standing
+pub fn josephus(n: usize, k: usize) -> Option<usize> {
+    josephus_order(n, k).pop()
+}
+
+#[test]
+fn push_advance_retreat() {
+    let mut circle = CircularList::new();
+
+    assert_eq!(circle.current(), None);
+    circle.advance(3);
+
+    for val in 1..=5 {
+        circle.push(val);
+    }
+
+    assert_eq!(circle.current(), Some(&1));
+    circle.advance(2);
+    assert_eq!(circle.current(), Some(&3));
+    circle.advance(7);
+    assert_eq!(circle.current(), Some(&5));
+    circle.retreat(6);
+    assert_eq!(circle.current(), Some(&4));
+    assert_eq!(circle.iter().copied().collect::<Vec<_>>(), vec![4, 5, 1, 2, 3]);
+}
+
+#[test]
+fn insert_remove_current() {
+    let mut circle = CircularList::new();
+
+    circle.insert_after_current(1);
+    circle.insert_after_current(3);
+    circle.insert_after_current(2);
+    assert_eq!(circle.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    if let Some(val) = circle.current_mut() {
+        *val = 10;
+    }
+
+    assert_eq!(circle.remove_current(), Some(10));
+    assert_eq!(circle.current(), Some(&2));
+    circle.advance(1);
+    assert_eq!(circle.remove_current(), Some(3));
+    assert_eq!(circle.current(), Some(&2));
+    assert_eq!(circle.remove_current(), Some(2));
+    assert_eq!(circle.remove_current(), None);
+    assert!(circle.is_empty());
+}
+
+#[test]
+fn from_linked_list() {
+    let list: LinkedList<i32> = (1..=4).collect();
+    let mut circle = CircularList::from(list);
+
+    assert_eq!(circle.len(), 4);
+    circle.retreat(1);
+    assert_eq!(circle.current(), Some(&4));
+    assert_eq!(format!("{:?}", circle), "[4, 1, 2, 3]");
+
+    let empty = CircularList::from(LinkedList::<i32>::new());
+    assert_eq!(empty.current(), None);
+}
+
+#[test]
+fn josephus_problem() {
+    assert_eq!(josephus_order(7, 3), vec![3, 6, 2, 7, 5, 1, 4]);
+    assert_eq!(josephus(7, 3), Some(4));
+    assert_eq!(josephus(41, 3), Some(31));
+    assert_eq!(josephus(5, 1), Some(5));
+    assert_eq!(josephus(1, 9), Some(1));
+    assert_eq!(josephus(0, 2), None);
+    assert_eq!(josephus(5, 0), None);
+}
@@ -0,0 +1,283 @@
+use crate::stack::Stack;
+
+// ------------------------------ Report ------------------------------
+// Offset is in bytes, line and column start at 1 and columns count chars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize
+}
+
+impl Position {
+    fn start() -> Self {
+        Position { offset: 0, line: 1, column: 1 }
+    }
+
+    fn advance(&mut self, c: char) {
+        self.offset += c.len_utf8();
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    fn advance_str(&mut self, text: &str) {
+        text.chars().for_each(|c| self.advance(c));
+    }
+}
+
+// An opener waiting for its closer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opener {
+    pub token: String,
+    pub closer: String,
+    pub position: Position
+}
+
+// A closer that doesn't match the innermost opener, expected is None when nothing was open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub found: String,
+    pub expected: Option<String>,
+    pub position: Position
+}
+
+// Checking stops at the first mismatch, unclosed then lists the openers still open at that
+// point from the outermost to the innermost one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelimiterReport {
+    pub mismatch: Option<Mismatch>,
+    pub unclosed: Vec<Opener>,
+    pub unterminated_string: Option<Position>
+}
+
+impl DelimiterReport {
+    pub fn is_valid(&self) -> bool {
+        self.mismatch.is_none() && self.unclosed.is_empty() && self.unterminated_string.is_none()
+    }
+}
+
+// ------------------------------ Delimiter Checker ------------------------------
+// Matches opening and closing tokens with a stack of the openers seen so far. Anything that
+// isn't a delimiter is skipped, as is everything inside string literals and line comments.
+// The escape character makes the next character plain text, inside strings or not
+#[derive(Debug, Clone)]
+pub struct DelimiterChecker {
+    pairs: Vec<(String, String)>,
+    quotes: Vec<char>,
+    escape: Option<char>,
+    line_comment: Option<String>
+}
+
+// Round, square and curly brackets, with " and ' strings and \ escapes
+impl Default for DelimiterChecker {
+    fn default() -> Self {
+        DelimiterChecker::new()
+            .pair("(", ")")
+            .pair("[", "]")
+            .pair("{", "}")
+            .quote('"')
+            .quote('\'')
+            .escape('\\')
+    }
+}
+
+impl DelimiterChecker {
+    // No pairs, strings, escapes or comments, add them with the methods below
+    pub fn new() -> Self {
+        DelimiterChecker { pairs: Vec::new(), quotes: Vec::new(), escape: None, line_comment: None }
+    }
+
+    // Tokens made of word characters, like begin and end, only match as whole words
+    pub fn pair(mut self, open: &str, close: &str) -> Self {
+        self.pairs.push((open.to_string(), close.to_string()));
+        self
+    }
+
+    pub fn quote(mut self, quote: char) -> Self {
+        self.quotes.push(quote);
+        self
+    }
+
+    pub fn escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    pub fn line_comment(mut self, start: &str) -> Self {
+        self.line_comment = Some(start.to_string());
+        self
+    }
+
+    pub fn check(&self, text: &str) -> DelimiterReport {
+        let mut openers: Stack<Opener> = Stack::new();
+        let mut position = Position::start();
+        let mut mismatch = None;
+        let mut string_start: Option<(char, Position)> = None;
+
+        while let Some(c) = text[position.offset..].chars().next() {
+            let rest = &text[position.offset..];
+
+            if Some(c) == self.escape {
+                position.advance(c);
+
+                if let Some(escaped) = rest.chars().nth(1) {
+                    position.advance(escaped);
+                }
+
+                continue;
+            }
+
+            if let Some((quote, _)) = string_start {
+                if c == quote {
+                    string_start = None;
+                }
+
+                position.advance(c);
+                continue;
+            }
+
+            if self.line_comment.as_deref().is_some_and(|start| !start.is_empty() && rest.starts_with(start)) {
+                position.advance_str(&rest[..rest.find('\n').unwrap_or(rest.len())]);
+                continue;
+            }
+
+            if self.quotes.contains(&c) {
+                string_start = Some((c, position));
+                position.advance(c);
+                continue;
+            }
+
+            let Some(token) = self.token_at(text, position.offset) else {
+                position.advance(c);
+                continue;
+            };
+
+            if openers.peek().is_some_and(|opener| opener.closer == token) {
+                openers.pop();
+            } else if let Some((open, close)) = self.pairs.iter().find(|(open, _)| open == token) {
+                openers.push(Opener { token: open.clone(), closer: close.clone(), position });
+            } else {
+                mismatch = Some(Mismatch {
+                    found: token.to_string(),
+                    expected: openers.peek().map(|opener| opener.closer.clone()),
+                    position
+                });
+                break;
+            }
+
+            position.advance_str(token);
+        }
+
+        let mut unclosed: Vec<Opener> = openers.into_iter().collect();
+        unclosed.reverse();
+
+        DelimiterReport {
+            mismatch,
+            unclosed,
+            unterminated_string: string_start.map(|(_, start)| start)
+        }
+    }
+
+    pub fn is_valid(&self, text: &str) -> bool {
+        self.check(text).is_valid()
+    }
+
+    // Longest opening or closing token starting at offset
+    fn token_at<'a>(&'a self, text: &str, offset: usize) -> Option<&'a str> {
+        self.pairs.iter()
+            .flat_map(|(open, close)| [open.as_str(), close.as_str()])
+            .filter(|token| !token.is_empty() && text[offset..].starts_with(token))
+            .filter(|token| is_whole_token(text, offset, token))
+            .max_by_key(|token| token.len())
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// A token that starts or ends in a word character mustn't continue a word on that side
+fn is_whole_token(text: &str, offset: usize, token: &str) -> bool {
+    let starts_word = token.chars().next().is_some_and(is_word_char);
+    let ends_word = token.chars().next_back().is_some_and(is_word_char);
+    let before = text[..offset].chars().next_back();
+    let after = text[offset + token.len()..].chars().next();
+    let joins_before = starts_word && before.is_some_and(is_word_char);
+    let joins_after = ends_word && after.is_some_and(is_word_char);
+
+    !joins_before && !joins_after
+}
+
+#[test]
+fn default_brackets() {
+    let checker = DelimiterChecker::default();
+
+    assert!(checker.is_valid("fn main() { let v = [1, (2)]; }"));
+    assert!(checker.is_valid("a"));
+    assert!(checker.is_valid(r#"print(")", '[', "\"(")"#));
+    assert!(!checker.is_valid("]"));
+
+    let report = checker.check("foo(\n  [1, 2}\n)");
+    assert_eq!(
+        report.mismatch,
+        Some(Mismatch {
+            found: "}".to_string(),
+            expected: Some("]".to_string()),
+            position: Position { offset: 12, line: 2, column: 8 }
+        })
+    );
+    assert_eq!(report.unclosed.iter().map(|opener| opener.token.as_str()).collect::<Vec<_>>(), vec!["(", "["]);
+}
+
+#[test]
+fn unclosed_openers() {
+    let report = DelimiterChecker::default().check("{ (\n[ ]");
+
+    assert_eq!(report.mismatch, None);
+    assert_eq!(
+        report.unclosed,
+        vec![
+            Opener { token: "{".to_string(), closer: "}".to_string(), position: Position { offset: 0, line: 1, column: 1 } },
+            Opener { token: "(".to_string(), closer: ")".to_string(), position: Position { offset: 2, line: 1, column: 3 } }
+        ]
+    );
+
+    let report = DelimiterChecker::default().check("x = ')");
+    assert_eq!(report.unterminated_string, Some(Position { offset: 4, line: 1, column: 5 }));
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn word_tokens_and_comments() {
+    let checker = DelimiterChecker::new()
+        .pair("begin", "end")
+        .pair("(", ")")
+        .line_comment("--");
+
+    assert!(checker.is_valid("begin x := f(1); -- end) stray\n backend := 2 end"));
+    assert!(checker.is_valid("beginning ending"));
+
+    let report = checker.check("begin ( end");
+    assert_eq!(report.mismatch.map(|mismatch| (mismatch.found, mismatch.expected)), Some(("end".to_string(), Some(")".to_string()))));
+}
+
+#[test]
+fn template_tokens() {
+    let checker = DelimiterChecker::new()
+        .pair("{{", "}}")
+        .pair("{%", "%}")
+        .pair("<", ">")
+        .escape('\\');
+
+    assert!(checker.is_valid("<p>{{ name }} {% if x %}\\<{% endif %}</p>"));
+
+    let report = checker.check("<p>{{ name %}</p>");
+    let mismatch = report.mismatch.expect("%} closes nothing");
+    assert_eq!(mismatch.expected.as_deref(), Some("}}"));
+    assert_eq!(mismatch.position.column, 12);
+}
@@ -1,151 +1,153 @@
-use std::error::Error;
-use std::fmt;
+use crate::{error::DsaError, traits::Container};
 
-#[derive(Debug)]
-struct IndexOutOfBounds;
+const DEFAULT_CAPACITY: usize = 16;
 
-impl fmt::Display for IndexOutOfBounds {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Index is out of bounds")
+pub struct Array<T> {
+    pub arr: Vec<T>,
+    capacity: usize,
+    len: usize,
+}
+
+impl<T: Default + Copy + PartialEq> Default for Array<T> {
+    fn default() -> Self {
+        Array::new(DEFAULT_CAPACITY)
     }
 }
 
-impl Error for IndexOutOfBounds {}
+impl<T: Default + Copy + PartialEq> Array<T> {
+    pub fn new(capacity: usize) -> Self {
+        let mut arr = Vec::with_capacity(capacity);
+        arr.resize(capacity, T::default()); 
 
-mod array {
-    use super::IndexOutOfBounds;
+        Array {
+            arr,
+            capacity,
+            len: 0
+        }
+    }
 
-    pub struct Array<T> {
-        pub arr: Vec<T>,
-        capacity: usize,
-        len: usize,
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    impl<T: Default + Copy + PartialEq> Array<T> {
-        pub fn new(capacity: usize) -> Self {
-            let mut arr = Vec::with_capacity(capacity);
-            arr.resize(capacity, T::default()); 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-            Array {
-                arr,
-                capacity,
-                len: 0
-            }
-        }
+    // Slots between len and capacity only hold filler values, so they count as out of bounds
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.arr[..self.len].get(index)
+    }
 
-        pub fn default() -> Self {
-            Array::new(16) // or any default capacity you choose
+    pub fn try_get(&self, index: usize) -> Result<&T, DsaError> {
+        if index < self.len {
+            Ok(&self.arr[index])
+        } else {
+            Err(DsaError::IndexOutOfBounds { index, len: self.len })
         }
+    }
 
-        pub fn len(&self) -> usize {
-            self.len
+    pub fn set(& mut self, element: T, index: usize) -> Result<(), DsaError> {
+        if index >= self.len {
+            Err(DsaError::IndexOutOfBounds { index, len: self.len })
+        } else {
+            self.arr[index] = element;
+            Ok(())
         }
+    }
 
-        pub fn is_empty(&self) -> bool {
-            self.len == 0
+    pub fn clear(& mut self) {
+        for index in 0..self.capacity {
+            self.arr[index] = T::default();
+            self.len = 0;
         }
+    }
 
-        pub fn get(&self, index: usize) -> Option<&T> {
-            self.arr.get(index)
-        }
+    pub fn add(& mut self, new_item: T) {            
+        if self.len + 1 >= self.capacity {
+            self.capacity *= 2;
+            let mut new_arr = Vec::with_capacity(self.capacity);
+            new_arr.resize(self.capacity, T::default());        
 
-        pub fn set(& mut self, element: T, index: usize) -> Result<(), IndexOutOfBounds> {
-            if index < self.capacity {
-                Err(IndexOutOfBounds)
-            } else {
-                self.arr[index] = element;
-                Ok(())
+            for (index, slot) in new_arr.iter_mut().enumerate() {
+                if let Some(item) = self.get(index) {
+                    *slot = *item;
+                }
             }
-        }
 
-        pub fn clear(& mut self) {
-            for index in 0..self.capacity {
-                self.arr[index] = T::default();
-                self.len = 0;
-            }
+            self.arr = new_arr;
         }
 
-        pub fn add(& mut self, new_item: T) {            
-            if self.len + 1 >= self.capacity {
-                self.capacity *= 2;
-                let mut new_arr = Vec::with_capacity(self.capacity);
-                new_arr.resize(self.capacity, T::default());        
-
-                for index in 0..self.capacity {
-                    match self.get(index) {
-                        Some(item) => new_arr[index] = *item,
-                        None => new_arr[index] = T::default()
-                    };
-                }
-
-                self.arr = new_arr;
-            }
+        
+        self.arr[self.len] = new_item;
+        self.len += 1;
+    }
 
-            
-            self.arr[self.len] = new_item;
-            self.len += 1;
+    pub fn remove_at(& mut self, rm_index: usize) -> Result<(), DsaError> {
+        if rm_index >= self.len {
+            return Err(DsaError::IndexOutOfBounds { index: rm_index, len: self.len });
         }
 
-        pub fn remove_at(& mut self, rm_index: usize) -> Result<(), IndexOutOfBounds> {
-            if rm_index >= self.len {
-                return Err(IndexOutOfBounds);
-            }
-
-            let mut new_arr = Vec::with_capacity(self.capacity);
-            new_arr.resize(self.capacity, T::default());
+        let mut new_arr = Vec::with_capacity(self.capacity);
+        new_arr.resize(self.capacity, T::default());
 
-            let mut j = 0;
-            for i in 0..self.len {
-                if i != rm_index {
-                    new_arr[j] = self.arr[i].clone();
-                    j += 1;
-                }
+        let mut j = 0;
+        for i in 0..self.len {
+            if i != rm_index {
+                new_arr[j] = self.arr[i];
+                j += 1;
             }
-
-            self.arr = new_arr;
-            self.len -= 1;
-            Ok(())
         }
 
-        pub fn remove(&mut self) -> Result<(), IndexOutOfBounds> {
-            if self.len == 0 {
-                return Err(IndexOutOfBounds);
-            }
-
-            println!("{}", self.len);
+        self.arr = new_arr;
+        self.len -= 1;
+        Ok(())
+    }
 
-            self.arr[self.len] = T::default();
-            self.len -= 1;
-            Ok(())
+    pub fn remove(&mut self) -> Result<(), DsaError> {
+        if self.len == 0 {
+            return Err(DsaError::Empty);
         }
 
-        pub fn index(&self, item: T) -> isize {
-            for index in 0..self.len {
-                if item == self.arr[index] {
-                    return index as isize;
-                }
-            }
+        self.len -= 1;
+        self.arr[self.len] = T::default();
+        Ok(())
+    }
 
-            -1
+    pub fn index(&self, item: T) -> isize {
+        for index in 0..self.len {
+            if item == self.arr[index] {
+                return index as isize;
+            }
         }
+
+        -1
     }
 }
 
-use array::Array;
+impl<T: Default + Copy + PartialEq> Container for Array<T> {
+    fn len(&self) -> usize {
+        self.len
+    }
 
-fn main() {
-    let mut array = Array::<i32>::new(2);
-    array.add(0);
+    fn clear(&mut self) {
+        Array::clear(self)
+    }
+}
+
+#[test]
+fn out_of_bounds() {
+    let mut array = Array::<i32>::new(4);
+
+    assert_eq!(array.remove(), Err(DsaError::Empty));
     array.add(1);
-    array.add(2);
-    array.add(3);
-
-    match array.remove() {
-        Ok(()) => (),
-        Err(e) => println!("{}", e)
-    };
-    array.add(4);
-
-    
-    println!("{:?}, {}", array.arr, array.index(1));
-}
\ No newline at end of file
+    assert_eq!(array.try_get(0), Ok(&1));
+    assert_eq!(array.try_get(1), Err(DsaError::IndexOutOfBounds { index: 1, len: 1 }));
+    assert_eq!(array.get(1), None);
+    assert_eq!(Array::<i32>::new(16).get(5), None);
+    assert_eq!(array.set(5, 3), Err(DsaError::IndexOutOfBounds { index: 3, len: 1 }));
+    assert_eq!(array.remove_at(2), Err(DsaError::IndexOutOfBounds { index: 2, len: 1 }));
+    assert_eq!(array.set(5, 0), Ok(()));
+    assert_eq!(array.remove(), Ok(()));
+    assert!(array.is_empty());
+}
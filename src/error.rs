@@ -0,0 +1,26 @@
+use std::error::Error;
+use std::fmt;
+
+// Shared error type returned by the fallible `try_*` APIs of every container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DsaError {
+    Empty,
+    Full,
+    IndexOutOfBounds { index: usize, len: usize },
+    UnknownElement
+}
+
+impl fmt::Display for DsaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DsaError::Empty => write!(f, "Container is empty"),
+            DsaError::Full => write!(f, "Container is full"),
+            DsaError::IndexOutOfBounds { index, len } => {
+                write!(f, "Index {} is out of bounds for length {}", index, len)
+            }
+            DsaError::UnknownElement => write!(f, "Element is not present in the container")
+        }
+    }
+}
+
+impl Error for DsaError {}
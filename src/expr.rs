@@ -0,0 +1,647 @@
+use std::{error::Error, fmt, num::{IntErrorKind, ParseIntError}};
+
+use crate::stack::Stack;
+
+// ------------------------------ Errors ------------------------------
+// Byte range of the source text an error points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize
+}
+
+impl Span {
+    fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprErrorKind {
+    UnexpectedChar(char),
+    UnexpectedToken,
+    MissingOperand,
+    UnmatchedParen,
+    UnknownFunction(String),
+    ArgumentCount { function: Function, found: usize },
+    InvalidNumber,
+    UnknownVariable(String),
+    DivisionByZero,
+    Overflow,
+    Domain
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    pub kind: ExprErrorKind,
+    pub span: Span
+}
+
+impl ExprError {
+    fn new(kind: ExprErrorKind, span: Span) -> Self {
+        ExprError { kind, span }
+    }
+}
+
+impl fmt::Display for ExprErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprErrorKind::UnexpectedChar(c) => write!(f, "Unexpected character {:?}", c),
+            ExprErrorKind::UnexpectedToken => write!(f, "Unexpected token"),
+            ExprErrorKind::MissingOperand => write!(f, "Missing operand"),
+            ExprErrorKind::UnmatchedParen => write!(f, "Unmatched parenthesis"),
+            ExprErrorKind::UnknownFunction(name) => write!(f, "Unknown function {}", name),
+            ExprErrorKind::ArgumentCount { function, found } => {
+                write!(f, "{} does not take {} arguments", function, found)
+            }
+            ExprErrorKind::InvalidNumber => write!(f, "Invalid number"),
+            ExprErrorKind::UnknownVariable(name) => write!(f, "Unknown variable {}", name),
+            ExprErrorKind::DivisionByZero => write!(f, "Division by zero"),
+            ExprErrorKind::Overflow => write!(f, "Arithmetic overflow"),
+            ExprErrorKind::Domain => write!(f, "Argument is outside the operation's domain")
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl Error for ExprError {}
+
+// ------------------------------ Tokens ------------------------------
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number,
+    Ident,
+    Op(char),
+    LeftParen,
+    RightParen,
+    Comma
+}
+
+fn tokenize(src: &str) -> Result<Vec<(Token, Span)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+
+        // Takes chars while they match, moving end past them
+        let mut take_while = |pred: &mut dyn FnMut(usize, char) -> bool| {
+            while let Some(&(i, c)) = chars.peek() {
+                if !pred(i, c) {
+                    break;
+                }
+
+                end = i + c.len_utf8();
+                chars.next();
+            }
+        };
+
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '0'..='9' | '.' => {
+                // e or E starts an exponent only when digits follow, optionally signed. Any
+                // other letter ends the number, so 2x is a number followed by a variable
+                let mut exponent = false;
+                take_while(&mut |i, c| match c {
+                    '0'..='9' => true,
+                    '.' => !exponent,
+                    'e' | 'E' if !exponent => {
+                        let rest = &src[i + 1..];
+                        exponent = rest.strip_prefix(['+', '-']).unwrap_or(rest).starts_with(|c: char| c.is_ascii_digit());
+                        exponent
+                    }
+                    '+' | '-' => exponent && src[..i].ends_with(['e', 'E']),
+                    _ => false
+                });
+                Token::Number
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                take_while(&mut |_, c| c.is_alphanumeric() || c == '_');
+                Token::Ident
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => Token::Op(c),
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            c => return Err(ExprError::new(ExprErrorKind::UnexpectedChar(c), Span::new(start, end)))
+        };
+
+        tokens.push((token, Span::new(start, end)));
+    }
+
+    Ok(tokens)
+}
+
+// ------------------------------ RPN ------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow
+}
+
+impl BinaryOp {
+    fn from_char(c: char) -> Self {
+        match c {
+            '+' => BinaryOp::Add,
+            '-' => BinaryOp::Sub,
+            '*' => BinaryOp::Mul,
+            '/' => BinaryOp::Div,
+            '%' => BinaryOp::Rem,
+            _ => BinaryOp::Pow
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Add | BinaryOp::Sub => 1,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 2,
+            BinaryOp::Pow => 4
+        }
+    }
+
+    fn is_right_assoc(self) -> bool {
+        self == BinaryOp::Pow
+    }
+}
+
+// Unary minus binds tighter than * but looser than ^, so -2^2 is -(2^2)
+const NEGATE_PRECEDENCE: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Min,
+    Max,
+    Sqrt
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "sqrt" => Some(Function::Sqrt),
+            _ => None
+        }
+    }
+
+    fn accepts(self, args: usize) -> bool {
+        match self {
+            Function::Min | Function::Max => args >= 1,
+            Function::Sqrt => args == 1
+        }
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Function::Min => write!(f, "min"),
+            Function::Max => write!(f, "max"),
+            Function::Sqrt => write!(f, "sqrt")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    Number(String),
+    Variable(String),
+    Binary(BinaryOp),
+    Negate,
+    Call { function: Function, args: usize }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Number(text) | Item::Variable(text) => write!(f, "{}", text),
+            Item::Binary(op) => {
+                let symbol = match op {
+                    BinaryOp::Add => '+',
+                    BinaryOp::Sub => '-',
+                    BinaryOp::Mul => '*',
+                    BinaryOp::Div => '/',
+                    BinaryOp::Rem => '%',
+                    BinaryOp::Pow => '^'
+                };
+                write!(f, "{}", symbol)
+            }
+            Item::Negate => write!(f, "neg"),
+            Item::Call { function, args } => write!(f, "{}/{}", function, args)
+        }
+    }
+}
+
+// Expression in reverse polish notation, each item keeps the span of the source it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rpn {
+    items: Vec<(Item, Span)>
+}
+
+impl fmt::Display for Rpn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (item, _)) in self.items.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", item)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ------------------------------ Shunting Yard ------------------------------
+// Operators waiting on the stack for their right hand side
+enum Pending {
+    Binary(BinaryOp),
+    Negate,
+    Paren,
+    Call(Function)
+}
+
+impl Pending {
+    fn precedence(&self) -> Option<u8> {
+        match self {
+            Pending::Binary(op) => Some(op.precedence()),
+            Pending::Negate => Some(NEGATE_PRECEDENCE),
+            Pending::Paren | Pending::Call(_) => None
+        }
+    }
+
+    fn into_item(self) -> Option<Item> {
+        match self {
+            Pending::Binary(op) => Some(Item::Binary(op)),
+            Pending::Negate => Some(Item::Negate),
+            Pending::Paren | Pending::Call(_) => None
+        }
+    }
+}
+
+// Turns an infix expression into RPN. Operators wait on a stack until one with a lower
+// precedence or a closing parenthesis comes along, the argument counts of the function
+// calls being parsed sit on a second stack
+pub fn parse(src: &str) -> Result<Rpn, ExprError> {
+    let tokens = tokenize(src)?;
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut pending: Stack<(Pending, Span)> = Stack::new();
+    let mut arg_counts: Stack<usize> = Stack::new();
+    let mut expect_operand = true;
+
+    for (i, &(token, span)) in tokens.iter().enumerate() {
+        let text = &src[span.start..span.end];
+        let is_call = token == Token::Ident && tokens.get(i + 1).is_some_and(|&(next, _)| next == Token::LeftParen);
+
+        if matches!(token, Token::Number | Token::Ident | Token::LeftParen) && !expect_operand {
+            return Err(ExprError::new(ExprErrorKind::UnexpectedToken, span));
+        }
+
+        match token {
+            Token::Number => {
+                output.push((Item::Number(text.to_string()), span));
+                expect_operand = false;
+            }
+            Token::Ident if is_call => {
+                let function = Function::from_name(text)
+                    .ok_or_else(|| ExprError::new(ExprErrorKind::UnknownFunction(text.to_string()), span))?;
+                pending.push((Pending::Call(function), span));
+            }
+            Token::Ident => {
+                output.push((Item::Variable(text.to_string()), span));
+                expect_operand = false;
+            }
+            // The function name right before it already pushed the call
+            Token::LeftParen if i > 0 && tokens[i - 1].0 == Token::Ident => arg_counts.push(1),
+            Token::LeftParen => pending.push((Pending::Paren, span)),
+            Token::Comma => {
+                if expect_operand {
+                    return Err(ExprError::new(ExprErrorKind::MissingOperand, span));
+                }
+
+                pop_operators(&mut pending, &mut output);
+
+                match (pending.peek(), arg_counts.pop()) {
+                    (Some((Pending::Call(_), _)), Some(count)) => arg_counts.push(count + 1),
+                    _ => return Err(ExprError::new(ExprErrorKind::UnexpectedToken, span))
+                }
+
+                expect_operand = true;
+            }
+            Token::RightParen => {
+                if expect_operand {
+                    return Err(ExprError::new(ExprErrorKind::MissingOperand, span));
+                }
+
+                pop_operators(&mut pending, &mut output);
+
+                match pending.pop() {
+                    Some((Pending::Paren, _)) => {}
+                    Some((Pending::Call(function), call_span)) => {
+                        let args = arg_counts.pop().expect("every call has an argument count");
+                        let call_span = Span::new(call_span.start, span.end);
+
+                        if !function.accepts(args) {
+                            return Err(ExprError::new(ExprErrorKind::ArgumentCount { function, found: args }, call_span));
+                        }
+
+                        output.push((Item::Call { function, args }, call_span));
+                    }
+                    _ => return Err(ExprError::new(ExprErrorKind::UnmatchedParen, span))
+                }
+            }
+            Token::Op(c) if expect_operand => match c {
+                '-' => pending.push((Pending::Negate, span)),
+                '+' => {}
+                _ => return Err(ExprError::new(ExprErrorKind::MissingOperand, span))
+            },
+            Token::Op(c) => {
+                let op = BinaryOp::from_char(c);
+
+                while let Some(top) = pending.peek().and_then(|(entry, _)| entry.precedence()) {
+                    if top < op.precedence() || (top == op.precedence() && op.is_right_assoc()) {
+                        break;
+                    }
+
+                    let (entry, entry_span) = pending.pop().expect("peeked an operator");
+                    output.extend(entry.into_item().map(|item| (item, entry_span)));
+                }
+
+                pending.push((Pending::Binary(op), span));
+                expect_operand = true;
+            }
+        }
+    }
+
+    if expect_operand {
+        return Err(ExprError::new(ExprErrorKind::MissingOperand, Span::new(src.len(), src.len())));
+    }
+
+    while let Some((entry, span)) = pending.pop() {
+        match entry.into_item() {
+            Some(item) => output.push((item, span)),
+            None => return Err(ExprError::new(ExprErrorKind::UnmatchedParen, span))
+        }
+    }
+
+    Ok(Rpn { items: output })
+}
+
+// Moves operators to the output until a parenthesis or call is on top
+fn pop_operators(pending: &mut Stack<(Pending, Span)>, output: &mut Vec<(Item, Span)>) {
+    while pending.peek().is_some_and(|(entry, _)| entry.precedence().is_some()) {
+        let (entry, span) = pending.pop().expect("peeked an operator");
+        output.extend(entry.into_item().map(|item| (item, span)));
+    }
+}
+
+// ------------------------------ Evaluation ------------------------------
+trait Operand: Copy {
+    fn parse(text: &str) -> Result<Self, ExprErrorKind>;
+    fn binary(op: BinaryOp, a: Self, b: Self) -> Result<Self, ExprErrorKind>;
+    fn negate(self) -> Result<Self, ExprErrorKind>;
+    fn sqrt(self) -> Result<Self, ExprErrorKind>;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+}
+
+impl Operand for f64 {
+    fn parse(text: &str) -> Result<Self, ExprErrorKind> {
+        text.parse().map_err(|_| ExprErrorKind::InvalidNumber)
+    }
+
+    fn binary(op: BinaryOp, a: Self, b: Self) -> Result<Self, ExprErrorKind> {
+        if matches!(op, BinaryOp::Div | BinaryOp::Rem) && b == 0.0 {
+            return Err(ExprErrorKind::DivisionByZero);
+        }
+
+        let result = match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div => a / b,
+            BinaryOp::Rem => a % b,
+            BinaryOp::Pow => a.powf(b)
+        };
+
+        if result.is_nan() {
+            Err(ExprErrorKind::Domain)
+        } else if result.is_infinite() && a.is_finite() && b.is_finite() {
+            Err(ExprErrorKind::Overflow)
+        } else {
+            Ok(result)
+        }
+    }
+
+    fn negate(self) -> Result<Self, ExprErrorKind> {
+        Ok(-self)
+    }
+
+    fn sqrt(self) -> Result<Self, ExprErrorKind> {
+        if self < 0.0 {
+            Err(ExprErrorKind::Domain)
+        } else {
+            Ok(f64::sqrt(self))
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        f64::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
+}
+
+// Integer arithmetic, division truncates and sqrt rounds down
+impl Operand for i64 {
+    // Well formed literals that don't fit overflow, anything else is malformed
+    fn parse(text: &str) -> Result<Self, ExprErrorKind> {
+        text.parse().map_err(|error: ParseIntError| match error.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ExprErrorKind::Overflow,
+            _ => ExprErrorKind::InvalidNumber
+        })
+    }
+
+    fn binary(op: BinaryOp, a: Self, b: Self) -> Result<Self, ExprErrorKind> {
+        if matches!(op, BinaryOp::Div | BinaryOp::Rem) && b == 0 {
+            return Err(ExprErrorKind::DivisionByZero);
+        }
+
+        let result = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div => a.checked_div(b),
+            BinaryOp::Rem => a.checked_rem(b),
+            BinaryOp::Pow => {
+                let exponent = u32::try_from(b).map_err(|_| {
+                    if b < 0 { ExprErrorKind::Domain } else { ExprErrorKind::Overflow }
+                })?;
+                a.checked_pow(exponent)
+            }
+        };
+
+        result.ok_or(ExprErrorKind::Overflow)
+    }
+
+    fn negate(self) -> Result<Self, ExprErrorKind> {
+        self.checked_neg().ok_or(ExprErrorKind::Overflow)
+    }
+
+    fn sqrt(self) -> Result<Self, ExprErrorKind> {
+        self.checked_isqrt().ok_or(ExprErrorKind::Domain)
+    }
+
+    fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+}
+
+impl Rpn {
+    pub fn items(&self) -> &[(Item, Span)] {
+        &self.items
+    }
+
+    // vars resolves variable names, returning None for unknown ones
+    pub fn eval_f64<V: Fn(&str) -> Option<f64>>(&self, vars: V) -> Result<f64, ExprError> {
+        self.eval(vars)
+    }
+
+    // Every operation is checked, overflowing reports the span of the operator
+    pub fn eval_i64<V: Fn(&str) -> Option<i64>>(&self, vars: V) -> Result<i64, ExprError> {
+        self.eval(vars)
+    }
+
+    // Operands are pushed on a stack and every operator replaces its operands with the result.
+    // parse only builds well formed RPN, so the stack always holds enough operands
+    fn eval<N: Operand, V: Fn(&str) -> Option<N>>(&self, vars: V) -> Result<N, ExprError> {
+        let mut operands: Stack<N> = Stack::new();
+        let pop = |operands: &mut Stack<N>| operands.pop().expect("parse checks operand counts");
+
+        for (item, span) in &self.items {
+            let fail = |kind| ExprError::new(kind, *span);
+
+            let val = match item {
+                Item::Number(text) => N::parse(text).map_err(fail)?,
+                Item::Variable(name) => vars(name).ok_or_else(|| fail(ExprErrorKind::UnknownVariable(name.clone())))?,
+                Item::Binary(op) => {
+                    let b = pop(&mut operands);
+                    let a = pop(&mut operands);
+                    N::binary(*op, a, b).map_err(fail)?
+                }
+                Item::Negate => pop(&mut operands).negate().map_err(fail)?,
+                Item::Call { function: Function::Sqrt, .. } => pop(&mut operands).sqrt().map_err(fail)?,
+                Item::Call { function, args } => {
+                    let mut acc = pop(&mut operands);
+
+                    for _ in 1..*args {
+                        let next = pop(&mut operands);
+                        acc = if *function == Function::Min { acc.min(next) } else { acc.max(next) };
+                    }
+
+                    acc
+                }
+            };
+
+            operands.push(val);
+        }
+
+        Ok(pop(&mut operands))
+    }
+}
+
+#[cfg(test)]
+fn no_vars<N>(_: &str) -> Option<N> {
+    None
+}
+
+#[cfg(test)]
+fn error_of(src: &str) -> (ExprErrorKind, (usize, usize)) {
+    let error = parse(src).and_then(|rpn| rpn.eval_f64(no_vars)).expect_err("expression is invalid");
+    (error.kind, (error.span.start, error.span.end))
+}
+
+#[test]
+fn shunting_yard() {
+    let to_rpn = |src| parse(src).map(|rpn| rpn.to_string());
+
+    assert_eq!(to_rpn("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"), Ok("3 4 2 * 1 5 - 2 3 ^ ^ / +".to_string()));
+    assert_eq!(to_rpn("a - b - c"), Ok("a b - c -".to_string()));
+    assert_eq!(to_rpn("-x^2 * -(y)"), Ok("x 2 ^ neg y neg *".to_string()));
+    assert_eq!(to_rpn("max(1, min(a, b) + 2, 3)"), Ok("1 a b min/2 2 + 3 max/3".to_string()));
+    assert_eq!(parse("sqrt(16)").map(|rpn| rpn.items()[1].1), Ok(Span::new(0, 8)));
+}
+
+#[test]
+fn eval_f64() {
+    let eval = |src: &str| parse(src).and_then(|rpn| rpn.eval_f64(|name| (name == "x").then_some(3.0)));
+
+    assert_eq!(eval("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"), Ok(3.0001220703125));
+    assert_eq!(eval("-2^2"), Ok(-4.0));
+    assert_eq!(eval("2^-1"), Ok(0.5));
+    assert_eq!(eval("--3 + +1"), Ok(4.0));
+    assert_eq!(eval("-(1 + 2) * x"), Ok(-9.0));
+    assert_eq!(eval("max(1, min(4, x), sqrt(16)) % 3"), Ok(1.0));
+    assert_eq!(eval("1.5e2 / 4"), Ok(37.5));
+    assert_eq!(eval("1e-2"), Ok(0.01));
+    assert_eq!(eval("2.5E+3"), Ok(2500.0));
+    assert_eq!(eval("2e1-1"), Ok(19.0));
+}
+
+#[test]
+fn eval_i64() {
+    let eval = |src: &str| parse(src).and_then(|rpn| rpn.eval_i64(no_vars)).map_err(|error| (error.kind, error.span));
+
+    assert_eq!(eval("7 / 2 - 9 % 4"), Ok(2));
+    assert_eq!(eval("-9 % 4 + sqrt(17)"), Ok(3));
+    assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512));
+    assert_eq!(eval("9223372036854775807 + 1"), Err((ExprErrorKind::Overflow, Span::new(20, 21))));
+    assert_eq!(eval("2 ^ 62 * 2"), Err((ExprErrorKind::Overflow, Span::new(7, 8))));
+    assert_eq!(eval("-(-9223372036854775807 - 1)"), Err((ExprErrorKind::Overflow, Span::new(0, 1))));
+    assert_eq!(eval("7 / (3 - 3)"), Err((ExprErrorKind::DivisionByZero, Span::new(2, 3))));
+    assert_eq!(eval("2 ^ -1"), Err((ExprErrorKind::Domain, Span::new(2, 3))));
+    assert_eq!(eval("sqrt(-4)"), Err((ExprErrorKind::Domain, Span::new(0, 8))));
+    assert_eq!(eval("1.5 + 1"), Err((ExprErrorKind::InvalidNumber, Span::new(0, 3))));
+    assert_eq!(eval("9223372036854775808 - 1"), Err((ExprErrorKind::Overflow, Span::new(0, 19))));
+    assert_eq!(eval("1e3"), Err((ExprErrorKind::InvalidNumber, Span::new(0, 3))));
+}
+
+#[test]
+fn errors_carry_spans() {
+    assert_eq!(error_of("1 +"), (ExprErrorKind::MissingOperand, (3, 3)));
+    assert_eq!(error_of("(1 + 2"), (ExprErrorKind::UnmatchedParen, (0, 1)));
+    assert_eq!(error_of("1 + 2)"), (ExprErrorKind::UnmatchedParen, (5, 6)));
+    assert_eq!(error_of("1 2"), (ExprErrorKind::UnexpectedToken, (2, 3)));
+    assert_eq!(error_of("2x"), (ExprErrorKind::UnexpectedToken, (1, 2)));
+    assert_eq!(error_of("3max(1, 2)"), (ExprErrorKind::UnexpectedToken, (1, 4)));
+    assert_eq!(error_of("2e"), (ExprErrorKind::UnexpectedToken, (1, 2)));
+    assert_eq!(error_of("1 $ 2"), (ExprErrorKind::UnexpectedChar('$'), (2, 3)));
+    assert_eq!(error_of("1, 2"), (ExprErrorKind::UnexpectedToken, (1, 2)));
+    assert_eq!(error_of("* 2"), (ExprErrorKind::MissingOperand, (0, 1)));
+    assert_eq!(error_of("min()"), (ExprErrorKind::MissingOperand, (4, 5)));
+    assert_eq!(error_of("pow(2, 3)"), (ExprErrorKind::UnknownFunction("pow".to_string()), (0, 3)));
+    assert_eq!(
+        error_of("sqrt(1, 2)"),
+        (ExprErrorKind::ArgumentCount { function: Function::Sqrt, found: 2 }, (0, 10))
+    );
+    assert_eq!(error_of("rate * 2"), (ExprErrorKind::UnknownVariable("rate".to_string()), (0, 4)));
+    assert_eq!(error_of("1 / 0"), (ExprErrorKind::DivisionByZero, (2, 3)));
+
+    let error = parse("(1").expect_err("paren is unmatched");
+    assert_eq!(error.to_string(), "Unmatched parenthesis at 0..1");
+}
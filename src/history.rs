@@ -0,0 +1,453 @@
+use crate::stack::Stack;
+
+// ------------------------------ Command ------------------------------
+// A reversible change to a target, undo has to restore exactly what execute changed
+pub trait Command {
+    type Target;
+
+    fn execute(&mut self, target: &mut Self::Target);
+    fn undo(&mut self, target: &mut Self::Target);
+
+    // Folds an already executed follow up command into this one, like consecutive keystrokes
+    // becoming a single word. Returns false when the two should stay separate
+    fn merge(&mut self, _next: &Self) -> bool {
+        false
+    }
+}
+
+// ------------------------------ History ------------------------------
+// One undo step, a group of commands is undone and redone together. Ids tell states apart
+// for the saved marker
+struct Entry<C> {
+    id: u64,
+    commands: Vec<C>
+}
+
+// Executed commands go on the undo stack, undoing moves them to the redo stack and redoing
+// moves them back. A new command clears the redo stack
+pub struct History<C> {
+    undo: Stack<Entry<C>>,
+    redo: Stack<Entry<C>>,
+    limit: Option<usize>,
+    // Entries at the bottom of the undo stack that fell past the limit and can't be undone
+    dropped: usize,
+    group: Option<Vec<C>>,
+    group_depth: usize,
+    can_merge: bool,
+    next_id: u64,
+    // Id of the state below the oldest undo entry, changes when entries are dropped
+    base_id: u64,
+    saved_id: u64
+}
+
+impl <C: Command> Default for History<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <C: Command> History<C> {
+    pub fn new() -> Self {
+        History {
+            undo: Stack::new(),
+            redo: Stack::new(),
+            limit: None,
+            dropped: 0,
+            group: None,
+            group_depth: 0,
+            can_merge: false,
+            next_id: 1,
+            base_id: 0,
+            saved_id: 0
+        }
+    }
+
+    // Keeps at most limit undo steps, the oldest ones are forgotten first
+    pub fn with_limit(limit: usize) -> Self {
+        History { limit: Some(limit), ..Self::new() }
+    }
+
+    pub fn execute(&mut self, target: &mut C::Target, mut command: C) {
+        command.execute(target);
+        self.redo.clear();
+
+        if let Some(group) = self.group.as_mut() {
+            let merged = self.can_merge && group.last_mut().is_some_and(|last| last.merge(&command));
+
+            if !merged {
+                group.push(command);
+            }
+        } else if self.can_merge && self.undo_depth() > 0 && self.undo.peek().is_some_and(|entry| entry.commands.len() == 1) {
+            let mut entry = self.undo.pop().expect("peeked an entry");
+
+            if entry.commands[0].merge(&command) {
+                // The merged entry leads to a state no id stood for yet
+                entry.id = self.take_id();
+                self.undo.push(entry);
+            } else {
+                self.undo.push(entry);
+                self.push_undo(vec![command]);
+            }
+        } else {
+            self.push_undo(vec![command]);
+        }
+
+        self.can_merge = true;
+    }
+
+    // Returns false when there is nothing to undo, an open group is ended first
+    pub fn undo(&mut self, target: &mut C::Target) -> bool {
+        self.close_group();
+        self.can_merge = false;
+
+        if self.undo_depth() == 0 {
+            return false;
+        }
+
+        let mut entry = self.undo.pop().expect("undo depth counts entries");
+
+        for command in entry.commands.iter_mut().rev() {
+            command.undo(target);
+        }
+
+        self.redo.push(entry);
+        true
+    }
+
+    // Returns false when there is nothing to redo
+    pub fn redo(&mut self, target: &mut C::Target) -> bool {
+        self.close_group();
+        self.can_merge = false;
+
+        let Some(mut entry) = self.redo.pop() else { return false };
+
+        for command in entry.commands.iter_mut() {
+            command.execute(target);
+        }
+
+        self.undo.push(entry);
+        self.enforce_limit();
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_depth() > 0 || self.group.as_ref().is_some_and(|group| !group.is_empty())
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo_depth(&self) -> usize {
+        self.undo.len() - self.dropped
+    }
+
+    pub fn redo_depth(&self) -> usize {
+        self.redo.len()
+    }
+
+    // Commands executed until the matching end_group form a single undo step. Groups nest,
+    // only the outermost one creates the step
+    pub fn begin_group(&mut self) {
+        if self.group_depth == 0 {
+            self.group = Some(Vec::new());
+            self.can_merge = false;
+        }
+
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        match self.group_depth {
+            0 => {}
+            1 => self.close_group(),
+            _ => self.group_depth -= 1
+        }
+    }
+
+    // Remembers the current state as the saved one, commands stop merging across it
+    pub fn mark_saved(&mut self) {
+        self.close_group();
+        self.saved_id = self.state_id();
+        self.can_merge = false;
+    }
+
+    // True when undoing or redoing hasn't led back to the saved state
+    pub fn is_dirty(&self) -> bool {
+        self.group.as_ref().is_some_and(|group| !group.is_empty()) || self.state_id() != self.saved_id
+    }
+
+    // Forgets every step, the current state counts as saved when it was before
+    pub fn clear(&mut self) {
+        self.close_group();
+        let was_dirty = self.is_dirty();
+
+        self.undo.clear();
+        self.redo.clear();
+        self.dropped = 0;
+        self.base_id = self.take_id();
+        self.saved_id = if was_dirty { self.saved_id } else { self.base_id };
+        self.can_merge = false;
+    }
+
+    fn state_id(&self) -> u64 {
+        self.undo.peek().map_or(self.base_id, |entry| entry.id)
+    }
+
+    fn take_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    fn push_undo(&mut self, commands: Vec<C>) {
+        let id = self.take_id();
+        self.undo.push(Entry { id, commands });
+        self.enforce_limit();
+    }
+
+    // Entries past the limit stay at the bottom of the undo stack until as many have piled up
+    // as the limit keeps, then they're cut off in one walk. That makes dropping the oldest
+    // entries O(1) amortised on a stack that only links downwards. The newest dropped entry
+    // stands for the state below the oldest undo step, so state_id still reads the top
+    fn enforce_limit(&mut self) {
+        let Some(limit) = self.limit else { return };
+
+        if self.undo_depth() > limit {
+            self.dropped = self.undo.len() - limit;
+        }
+
+        if self.dropped > limit {
+            let depth = self.undo_depth();
+            self.base_id = self.undo.iter().nth(depth).expect("dropped entries sit below").id;
+            self.undo.truncate(depth);
+            self.dropped = 0;
+        }
+    }
+
+    fn close_group(&mut self) {
+        self.group_depth = 0;
+
+        if let Some(commands) = self.group.take() {
+            if !commands.is_empty() {
+                self.push_undo(commands);
+            }
+
+            self.can_merge = false;
+        }
+    }
+}
+
+// Text edits on a String, consecutive inserts merge like typing does
+#[cfg(test)]
+#[derive(Debug)]
+enum Edit {
+    Insert { at: usize, text: String },
+    Delete { at: usize, text: String }
+}
+
+#[cfg(test)]
+impl Edit {
+    fn insert(at: usize, text: &str) -> Self {
+        Edit::Insert { at, text: text.to_string() }
+    }
+
+    fn delete(at: usize, len: usize, doc: &str) -> Self {
+        Edit::Delete { at, text: doc[at..at + len].to_string() }
+    }
+}
+
+#[cfg(test)]
+impl Command for Edit {
+    type Target = String;
+
+    fn execute(&mut self, doc: &mut String) {
+        match self {
+            Edit::Insert { at, text } => doc.insert_str(*at, text),
+            Edit::Delete { at, text } => doc.replace_range(*at..*at + text.len(), "")
+        }
+    }
+
+    fn undo(&mut self, doc: &mut String) {
+        match self {
+            Edit::Insert { at, text } => doc.replace_range(*at..*at + text.len(), ""),
+            Edit::Delete { at, text } => doc.insert_str(*at, text)
+        }
+    }
+
+    fn merge(&mut self, next: &Self) -> bool {
+        match (self, next) {
+            (Edit::Insert { at, text }, Edit::Insert { at: next_at, text: next_text }) if *at + text.len() == *next_at => {
+                text.push_str(next_text);
+                true
+            }
+            _ => false
+        }
+    }
+}
+
+#[test]
+fn execute_undo_redo() {
+    let mut doc = String::new();
+    let mut history = History::new();
+
+    history.execute(&mut doc, Edit::insert(0, "hello world"));
+    let edit = Edit::delete(0, 6, &doc);
+    history.execute(&mut doc, edit);
+    assert_eq!(doc, "world");
+
+    assert!(history.undo(&mut doc));
+    assert_eq!(doc, "hello world");
+    assert!(history.undo(&mut doc));
+    assert_eq!(doc, "");
+    assert!(!history.undo(&mut doc));
+
+    assert!(history.redo(&mut doc));
+    assert_eq!(doc, "hello world");
+
+    // A new edit throws away what could have been redone
+    history.execute(&mut doc, Edit::insert(11, "!"));
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut doc));
+    assert_eq!(doc, "hello world!");
+}
+
+#[test]
+fn merges_typing() {
+    let mut doc = String::new();
+    let mut history = History::new();
+
+    for (at, key) in ["a", "b", "c"].into_iter().enumerate() {
+        history.execute(&mut doc, Edit::insert(at, key));
+    }
+
+    history.execute(&mut doc, Edit::insert(0, ">"));
+    assert_eq!(history.undo_depth(), 2);
+
+    history.undo(&mut doc);
+    assert_eq!(doc, "abc");
+
+    // Nothing merges into an entry that was undone and redone
+    history.redo(&mut doc);
+    history.execute(&mut doc, Edit::insert(4, "d"));
+    assert_eq!(history.undo_depth(), 3);
+
+    history.undo(&mut doc);
+    history.undo(&mut doc);
+    history.undo(&mut doc);
+    assert_eq!(doc, "");
+}
+
+#[test]
+fn bounded_depth() {
+    let mut doc = String::new();
+    let mut history = History::with_limit(2);
+
+    for (at, key) in ["a", "b", "c", "d"].into_iter().enumerate() {
+        history.execute(&mut doc, Edit::insert(at, key));
+        history.mark_saved();
+    }
+
+    assert_eq!(history.undo_depth(), 2);
+    while history.undo(&mut doc) {}
+    assert_eq!(doc, "ab");
+    assert!(history.is_dirty());
+
+    while history.redo(&mut doc) {}
+    assert!(!history.is_dirty());
+}
+
+#[test]
+fn bounded_depth_batches() {
+    let mut doc = String::new();
+    let mut history = History::with_limit(3);
+
+    // Enough steps to cut the dropped entries off the bottom a few times
+    for (at, key) in "abcdefghijklmnopqrstuvwxyz".char_indices() {
+        history.execute(&mut doc, Edit::insert(at, &key.to_string()));
+        history.mark_saved();
+        assert_eq!(history.undo_depth(), (at + 1).min(3));
+        assert!(history.undo.len() <= 7);
+    }
+
+    while history.undo(&mut doc) {}
+    assert_eq!(doc, "abcdefghijklmnopqrstuvw");
+    assert_eq!(history.redo_depth(), 3);
+    assert!(history.is_dirty());
+
+    // Nothing merges into a step that can't be undone anymore
+    let mut history = History::with_limit(0);
+    history.execute(&mut doc, Edit::insert(23, "x"));
+    history.execute(&mut doc, Edit::insert(24, "y"));
+    assert!(!history.can_undo());
+    assert!(!history.undo(&mut doc));
+    assert_eq!(doc, "abcdefghijklmnopqrstuvwxy");
+}
+
+#[test]
+fn groups() {
+    let mut doc = "a b".to_string();
+    let mut history = History::new();
+
+    history.begin_group();
+    history.execute(&mut doc, Edit::insert(0, "["));
+    history.begin_group();
+    history.execute(&mut doc, Edit::insert(4, "]"));
+    history.end_group();
+    assert_eq!(history.undo_depth(), 0);
+    let edit = Edit::delete(2, 1, &doc);
+    history.execute(&mut doc, edit);
+    history.end_group();
+
+    assert_eq!(doc, "[ab]");
+    assert_eq!(history.undo_depth(), 1);
+    history.undo(&mut doc);
+    assert_eq!(doc, "a b");
+    history.redo(&mut doc);
+    assert_eq!(doc, "[ab]");
+
+    // Empty groups leave no step behind and an undo closes an open group
+    history.begin_group();
+    history.end_group();
+    history.begin_group();
+    history.execute(&mut doc, Edit::insert(4, "!"));
+    assert!(history.undo(&mut doc));
+    assert_eq!(doc, "[ab]");
+    assert_eq!(history.redo_depth(), 1);
+}
+
+#[test]
+fn saved_marker() {
+    let mut doc = String::new();
+    let mut history = History::new();
+
+    assert!(!history.is_dirty());
+    history.execute(&mut doc, Edit::insert(0, "draft"));
+    assert!(history.is_dirty());
+
+    history.mark_saved();
+    assert!(!history.is_dirty());
+
+    // Typing after a save starts a new step instead of merging into the saved one
+    history.execute(&mut doc, Edit::insert(5, "s"));
+    assert_eq!(history.undo_depth(), 2);
+    assert!(history.is_dirty());
+    history.undo(&mut doc);
+    assert!(!history.is_dirty());
+    history.undo(&mut doc);
+    assert!(history.is_dirty());
+    history.redo(&mut doc);
+    assert!(!history.is_dirty());
+
+    // Once the saved state can't be reached again the history stays dirty
+    history.undo(&mut doc);
+    history.execute(&mut doc, Edit::insert(0, "new"));
+    history.undo(&mut doc);
+    assert_eq!(doc, "");
+    assert!(history.is_dirty());
+
+    history.clear();
+    assert!(history.is_dirty());
+    history.mark_saved();
+    history.clear();
+    assert!(!history.is_dirty());
+}
@@ -0,0 +1,35 @@
+pub mod aggregating_stack;
+pub mod circular_list;
+pub mod delimiter_checker;
+pub mod dynamic_array;
+pub mod error;
+pub mod expr;
+pub mod history;
+pub mod linked_list;
+pub mod lru_cache;
+pub mod monotonic_stack;
+pub mod persistent_list;
+pub mod priority_queue;
+pub mod queue;
+pub mod skip_list;
+pub mod stack;
+pub mod traits;
+pub mod union_find;
+pub mod unrolled_list;
+
+pub use aggregating_stack::{AggregatingStack, MinMaxStack, Monoid};
+pub use circular_list::CircularList;
+pub use delimiter_checker::DelimiterChecker;
+pub use dynamic_array::Array;
+pub use error::DsaError;
+pub use history::{Command, History};
+pub use linked_list::LinkedList;
+pub use lru_cache::LruCache;
+pub use persistent_list::PersistentList;
+pub use priority_queue::{PriorityQueue, Sort};
+pub use queue::{Queue, StaticQueue};
+pub use skip_list::SkipList;
+pub use stack::{Stack, StaticStack};
+pub use traits::{Buffer, Container, FifoBuffer, LifoBuffer, PriorityBuffer};
+pub use union_find::UnionFind;
+pub use unrolled_list::UnrolledList;
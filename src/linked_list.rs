@@ -1,13 +1,18 @@
 #![allow(dead_code, unused_variables)]
 
-use std::{cell::RefCell, fmt::Display, marker::PhantomData, rc::Rc};
+use std::{cmp::Ordering, error::Error, fmt::{self, Debug, Display}, hash::{Hash, Hasher}, marker::PhantomData, mem, ptr::NonNull};
 
-type NodePointer<T> = Rc<RefCell<Node<T>>>;
+use crate::{error::DsaError, traits::{Buffer, Container, FifoBuffer}};
+
+// Nodes are owned by the list and only ever reached through these pointers, so handing
+// out references tied to a borrow of the list is sound
+pub(crate) type NodePointer<T> = NonNull<Node<T>>;
 
 #[derive(Debug)]
 pub struct Node<T> {
-    val: T,
-    next: Option<NodePointer<T>>
+    pub(crate) val: T,
+    pub(crate) prev: Option<NodePointer<T>>,
+    pub(crate) next: Option<NodePointer<T>>
 }
 
 impl <T: Display> Display for Node<T> {
@@ -18,278 +23,2115 @@ impl <T: Display> Display for Node<T> {
 
 impl <T> Node<T> {
     fn new(val: T) -> Self {
-        Node { val, next: None }
+        Node { val, prev: None, next: None }
+    }
+
+    // Moves the node to the heap, the returned pointer owns it until freed by Box::from_raw
+    pub(crate) fn alloc(val: T) -> NodePointer<T> {
+        NonNull::from(Box::leak(Box::new(Node::new(val))))
     }
 }
 
 // ------------------------------ Node ------------------------------
-#[derive(Debug)]
 pub struct LinkedList<T> {
     head: Option<NodePointer<T>>,
     tail: Option<NodePointer<T>>,
-    length: u32
+    length: usize,
+    phantom: PhantomData<Box<Node<T>>>
 }
 
 impl <T: Display> Display for LinkedList<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(head_node_rc) = self.head.clone()  {
-            write!(f, "{}", head_node_rc.borrow())?;
-            let mut current = head_node_rc.borrow().next.clone();
-            
-            while let Some(node_rc) = current {
-                let node = node_rc.borrow();
-                write!(f, " -> {}", node.val)?;
-                current = node.next.clone();
+        Display::fmt(&self.display(), f)
+    }
+}
+
+// ------------------------------ Display ------------------------------
+// Formatting options for a list, built through LinkedList::display. The alternate flag
+// ({:#}) prints one value per line prefixed with its index
+pub struct ListDisplay<'a, T> {
+    list: &'a LinkedList<T>,
+    separator: &'a str,
+    open: &'a str,
+    close: &'a str,
+    show_len: bool,
+    max_items: Option<usize>
+}
+
+impl <'a, T> ListDisplay<'a, T> {
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    pub fn brackets(mut self, open: &'a str, close: &'a str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    pub fn show_len(mut self, show_len: bool) -> Self {
+        self.show_len = show_len;
+        self
+    }
+
+    // Values past the first max_items are replaced by ...
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+impl <T: Display> Display for ListDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shown = self.max_items.unwrap_or(self.list.length).min(self.list.length);
+        let truncated = shown < self.list.length;
+
+        if f.alternate() {
+            // Lines are separated, not terminated, so the output never ends in a newline
+            let mut first = true;
+            let mut line = |f: &mut std::fmt::Formatter<'_>, args: std::fmt::Arguments<'_>| {
+                if !std::mem::take(&mut first) {
+                    writeln!(f)?;
+                }
+
+                f.write_fmt(args)
+            };
+
+            if !self.open.is_empty() {
+                line(f, format_args!("{}", self.open))?;
+            }
+
+            for (index, val) in self.list.iter().take(shown).enumerate() {
+                line(f, format_args!("{}: {}", index, val))?;
+            }
+
+            if truncated {
+                line(f, format_args!("..."))?;
+            }
+
+            if !self.close.is_empty() {
+                line(f, format_args!("{}", self.close))?;
+            }
+
+            if self.show_len {
+                line(f, format_args!("Length - {}", self.list.length))?;
+            }
+
+            return Ok(());
+        }
+
+        write!(f, "{}", self.open)?;
+
+        for (index, val) in self.list.iter().take(shown).enumerate() {
+            if index > 0 {
+                write!(f, "{}", self.separator)?;
+            }
+
+            write!(f, "{}", val)?;
+        }
+
+        if truncated {
+            if shown > 0 {
+                write!(f, "{}", self.separator)?;
             }
+
+            write!(f, "...")?;
+        }
+
+        write!(f, "{}", self.close)?;
+
+        if self.show_len {
+            write!(f, "\nLength - {}", self.list.length)?;
         }
 
-        write!(f, "\nLength - {}", self.length)
+        Ok(())
+    }
+}
+
+// ------------------------------ Invariants ------------------------------
+// Ways the head, tail and length bookkeeping can disagree with the actual nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    HeadHasPrev,
+    BrokenPrevLink { index: usize },
+    TailMismatch,
+    LengthMismatch { length: usize, nodes: usize }
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvariantViolation::HeadHasPrev => write!(f, "Head node has a previous node"),
+            InvariantViolation::BrokenPrevLink { index } => {
+                write!(f, "Node {} does not link back to the node before it", index)
+            }
+            InvariantViolation::TailMismatch => write!(f, "Tail is not the last reachable node"),
+            InvariantViolation::LengthMismatch { length, nodes } => {
+                write!(f, "Length is {} but {} nodes are reachable", length, nodes)
+            }
+        }
     }
 }
 
+impl Error for InvariantViolation {}
+
 // ------------------------------ Linked List ------------------------------
-impl <T: Clone> LinkedList<T> {
+impl <T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> LinkedList<T> {
     pub fn new() -> Self {
-        LinkedList { head: None, tail: None, length: 0 }
+        LinkedList { head: None, tail: None, length: 0, phantom: PhantomData }
+    }
+
+    pub fn push_front(&mut self, val: T) {
+        unsafe { self.link_between(None, self.head, Node::alloc(val)) }
+    }
+
+    pub fn push_back(&mut self, val: T) {
+        unsafe { self.link_between(self.tail, None, Node::alloc(val)) }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| unsafe { self.unlink(node).val })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| unsafe { self.unlink(node).val })
+    }
+
+    pub fn peek_back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).val })
     }
 
     pub fn push(&mut self, val: T) {
-        let new_node = Rc::new(RefCell::new(Node::new(val)));
+        self.push_back(val);
+    }
+
+    // Out of range indexes are ignored, use try_insert_at to detect that
+    pub fn insert_at(&mut self, val: T, insert_index: usize) {
+        let _ = self.try_insert_at(val, insert_index);
+    }
+
+    pub fn try_insert_at(&mut self, val: T, insert_index: usize) -> Result<(), DsaError> {
+        if insert_index > self.length {
+            return Err(DsaError::IndexOutOfBounds { index: insert_index, len: self.length });
+        }
 
-        if let Some(tail_node_rc) = self.tail.clone() {
-            tail_node_rc.borrow_mut().next = Some(new_node.clone());
+        if insert_index == self.length {
+            self.push_back(val);
         } else {
-            self.head = Some(new_node.clone());
+            let next_node = self.node_at(insert_index);
+            unsafe { self.link_between((*next_node.as_ptr()).prev, Some(next_node), Node::alloc(val)) }
         }
 
-        self.tail = Some(new_node.clone());
-        self.length += 1;
+        Ok(())
     }
 
-    pub fn insert_at(&mut self, val: T, insert_index: u32) {
-        let new_node = Rc::new(RefCell::new(Node::new(val)));
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
 
-        if insert_index == 0 {
-            new_node.borrow_mut().next = self.head.clone();
-            self.head = Some(new_node);
-            self.length += 1;
-            return;
+    pub fn try_pop(&mut self) -> Result<T, DsaError> {
+        self.pop().ok_or(DsaError::Empty)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.length {
+            Some(unsafe { &(*self.node_at(index).as_ptr()).val })
+        } else {
+            None
         }
+    }
 
-        let mut current_node = self.head.clone();
-        let mut current_index = 1;
+    pub fn try_get(&self, index: usize) -> Result<&T, DsaError> {
+        self.get(index).ok_or(DsaError::IndexOutOfBounds { index, len: self.length })
+    }
 
-        while let Some(current_node_rc) = current_node {
-            if current_index == insert_index {
-                new_node.borrow_mut().next = current_node_rc.borrow().next.clone();
-                current_node_rc.borrow_mut().next = Some(new_node.clone());
-                self.length += 1;
-                break;
-            }
+    pub fn drop(&mut self, drop_index: usize) -> Option<T> {
+        self.try_drop(drop_index).ok()
+    }
 
-            current_index += 1;
-            current_node = current_node_rc.borrow().next.clone();
+    pub fn try_drop(&mut self, drop_index: usize) -> Result<T, DsaError> {
+        if drop_index >= self.length {
+            return Err(DsaError::IndexOutOfBounds { index: drop_index, len: self.length });
         }
+
+        let node_to_drop = self.node_at(drop_index);
+        Ok(unsafe { self.unlink(node_to_drop).val })
     }
 
-    pub fn pop(&mut self) -> Option<T> {
-        self.head.clone().map(|node| {
-            let new_head = node.borrow().next.clone();
-            self.head = new_head;
-            let pop_value = node.borrow().val.clone();
-            pop_value
-        })
+    // Moves every value of other to the back of this list in O(1), leaving other empty
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        unsafe { self.splice_between(self.tail, None, mem::take(other)) }
     }
 
-    pub fn peek(&self) -> Option<&T> {
-        if let Some(head_node) = self.head.clone() {
-            // let head_value = &*(&head_node.borrow().val as *const T);
-            unsafe { Some(&*(&head_node.borrow().val as *const T)) }
+    // Moves every value of other to the front of this list in O(1), leaving other empty
+    pub fn prepend(&mut self, other: &mut LinkedList<T>) {
+        unsafe { self.splice_between(None, self.head, mem::take(other)) }
+    }
+
+    // Keeps the first at values and returns the rest, use try_split_off to get the error
+    pub fn split_off(&mut self, at: usize) -> Option<LinkedList<T>> {
+        self.try_split_off(at).ok()
+    }
+
+    pub fn try_split_off(&mut self, at: usize) -> Result<LinkedList<T>, DsaError> {
+        if at > self.length {
+            return Err(DsaError::IndexOutOfBounds { index: at, len: self.length });
+        }
+
+        if at == 0 {
+            return Ok(mem::take(self));
+        }
+
+        let last_kept = self.node_at(at - 1);
+        Ok(unsafe { self.split_after_node(last_kept, at) })
+    }
+
+    // Out of range indexes leave both lists untouched, use try_splice to detect that
+    pub fn splice(&mut self, at: usize, other: &mut LinkedList<T>) {
+        let _ = self.try_splice(at, other);
+    }
+
+    // Moves every value of other in so that its first value ends up at index at
+    pub fn try_splice(&mut self, at: usize, other: &mut LinkedList<T>) -> Result<(), DsaError> {
+        if at > self.length {
+            return Err(DsaError::IndexOutOfBounds { index: at, len: self.length });
+        }
+
+        if at == self.length {
+            self.append(other);
         } else {
-            None
+            let next_node = self.node_at(at);
+            unsafe { self.splice_between((*next_node.as_ptr()).prev, Some(next_node), mem::take(other)) }
         }
+
+        Ok(())
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    // Configurable formatting, defaults to the same output as the Display impl
+    pub fn display(&self) -> ListDisplay<'_, T> {
+        ListDisplay { list: self, separator: " -> ", open: "", close: "", show_len: true, max_items: None }
     }
 
     pub fn iter(&self) -> Iter<'_, T> {
-        Iter { current_node: self.head.clone(), phantom: PhantomData }
+        Iter { current_node: self.head, back_node: self.tail, remaining: self.length, phantom: PhantomData }
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        IterMut { current_node: self.head.clone(), phantom: PhantomData }
+        IterMut { current_node: self.head, back_node: self.tail, remaining: self.length, phantom: PhantomData }
     }
 
-    pub fn get(&self, index: u32) -> Option<&T> {
-        let mut current_index = 0;
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor { index: 0, current: self.head, list: self }
+    }
 
-        for node in self.iter() {
-            if current_index == index {
-                return Some(node);
-            }
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor { index: self.length.saturating_sub(1), current: self.tail, list: self }
+    }
 
-            current_index += 1;
-        }
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { index: 0, current: self.head, list: self }
+    }
 
-        None
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { index: self.length.saturating_sub(1), current: self.tail, list: self }
     }
 
-    pub fn drop(&mut self, drop_index: u32) -> Option<T> {
-        if drop_index == 0 {
-            if let Some(head_node) = self.head.clone() {
-                let new_head = head_node.borrow().next.clone();
-                self.head = new_head;
-                return Some(head_node.borrow().val.clone());
+    // Walks the whole list and checks that head, tail, length and the prev links agree
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        if let Some(head) = self.head {
+            if unsafe { (*head.as_ptr()).prev.is_some() } {
+                return Err(InvariantViolation::HeadHasPrev);
             }
         }
 
-        let mut current_node = self.head.clone();
-        let mut current_index = 1;
+        let mut previous_node = None;
+        let mut current_node = self.head;
+        let mut nodes = 0;
 
-        while let Some(current_node_rc) = current_node {
-            if current_index == drop_index {
-                let node_to_drop = current_node_rc.borrow().next.clone();
-                
-                if let Some(node_to_drop_rc) = node_to_drop {
-                    let next_node = node_to_drop_rc.borrow().next.clone();
-                    current_node_rc.borrow_mut().next = next_node;
-                    return Some(node_to_drop_rc.borrow().val.clone());
-                } else {
-                    return None;
-                }
+        // Every node has to link back to the one before it, so the walk can't come back to a node
+        // it already visited: that node links back to its first predecessor, or to nothing for
+        // the head. A cycle ends the walk as a broken link and nodes counts the whole chain
+        while let Some(node) = current_node {
+            if unsafe { (*node.as_ptr()).prev } != previous_node {
+                return Err(InvariantViolation::BrokenPrevLink { index: nodes });
             }
 
-            current_index += 1;
-            current_node = current_node_rc.borrow().next.clone();
+            previous_node = Some(node);
+            current_node = unsafe { (*node.as_ptr()).next };
+            nodes += 1;
+        }
+
+        if previous_node != self.tail {
+            return Err(InvariantViolation::TailMismatch);
         }
 
-        None
+        if nodes != self.length {
+            return Err(InvariantViolation::LengthMismatch { length: self.length, nodes });
+        }
+
+        Ok(())
     }
-}
 
-// ------------------------------ Iterator ------------------------------
-pub struct IntoIter<T>(LinkedList<T>);
+    // Links a detached node in between two adjacent nodes of this list, None meaning the list's end
+    unsafe fn link_between(&mut self, prev: Option<NodePointer<T>>, next: Option<NodePointer<T>>, node: NodePointer<T>) {
+        (*node.as_ptr()).prev = prev;
+        (*node.as_ptr()).next = next;
 
-impl <T: Clone> Iterator for IntoIter<T> {
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop()
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(node),
+            None => self.head = Some(node)
+        }
+
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(node),
+            None => self.tail = Some(node)
+        }
+
+        self.length += 1;
     }
-}
 
-pub struct Iter<'a, T> {
-    current_node: Option<NodePointer<T>>,
-    phantom: PhantomData<&'a T>
-}
+    // Unlinks a node of this list and hands back ownership of it
+    unsafe fn unlink(&mut self, node: NodePointer<T>) -> Box<Node<T>> {
+        self.detach(node);
+        Box::from_raw(node.as_ptr())
+    }
 
-impl <'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.current_node.clone().map(|node| {
-            self.current_node = node.borrow().next.clone();
-            unsafe { &*(&node.borrow().val as *const T) }
-        })
+    // Takes a node of this list out of the chain without freeing it
+    unsafe fn detach(&mut self, node: NodePointer<T>) {
+        let (prev, next) = ((*node.as_ptr()).prev, (*node.as_ptr()).next);
+
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = next,
+            None => self.head = next
+        }
+
+        match next {
+            Some(next) => (*next.as_ptr()).prev = prev,
+            None => self.tail = prev
+        }
+
+        self.length -= 1;
     }
-}
 
-pub struct IterMut<'a, T> {
-    current_node: Option<NodePointer<T>>,
-    phantom: PhantomData<&'a T>
-}
+    // Moves every node of other in between two adjacent nodes of this list
+    unsafe fn splice_between(&mut self, prev: Option<NodePointer<T>>, next: Option<NodePointer<T>>, mut other: LinkedList<T>) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
 
-impl <'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
+        (*other_head.as_ptr()).prev = prev;
+        (*other_tail.as_ptr()).next = next;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.current_node.clone().map(|node| {
-            self.current_node = node.borrow().next.clone();
-            unsafe { &mut *(&mut node.borrow_mut().val as *mut T) }
-        })
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(other_head),
+            None => self.head = Some(other_head)
+        }
+
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(other_tail),
+            None => self.tail = Some(other_tail)
+        }
+
+        self.length += mem::replace(&mut other.length, 0);
     }
-}
 
-// Return list with 1, 2, 3
-fn init_test_list() -> LinkedList<i32> {
-    let mut list = LinkedList::<i32>::new();
+    // Detaches every node after the given one, which is the at-th node of the list counting from 1
+    unsafe fn split_after_node(&mut self, node: NodePointer<T>, at: usize) -> LinkedList<T> {
+        let Some(second_head) = (*node.as_ptr()).next.take() else {
+            return LinkedList::new();
+        };
 
-    list.push(1);
-    list.push(2);
-    list.push(3);
-    list.push(4);
-    list.push(5);
-    list.push(6);
+        (*second_head.as_ptr()).prev = None;
 
-    list
+        let second = LinkedList {
+            head: Some(second_head),
+            tail: self.tail.replace(node),
+            length: self.length - at,
+            phantom: PhantomData
+        };
+
+        self.length = at;
+        second
+    }
+
+    // Detaches every node before the given one, which is the at-th node of the list counting from 0
+    unsafe fn split_before_node(&mut self, node: NodePointer<T>, at: usize) -> LinkedList<T> {
+        let Some(first_tail) = (*node.as_ptr()).prev.take() else {
+            return LinkedList::new();
+        };
+
+        (*first_tail.as_ptr()).next = None;
+
+        let first = LinkedList {
+            head: self.head.replace(node),
+            tail: Some(first_tail),
+            length: at,
+            phantom: PhantomData
+        };
+
+        self.length -= at;
+        first
+    }
+
+    // Walks from whichever end is closer, callers guarantee index < length
+    fn node_at(&self, index: usize) -> NodePointer<T> {
+        if index < self.length / 2 {
+            let mut current_node = self.head.expect("index is within length");
+
+            for _ in 0..index {
+                current_node = unsafe { (*current_node.as_ptr()).next.expect("index is within length") };
+            }
+
+            current_node
+        } else {
+            let mut current_node = self.tail.expect("index is within length");
+
+            for _ in index + 1..self.length {
+                current_node = unsafe { (*current_node.as_ptr()).prev.expect("index is within length") };
+            }
+
+            current_node
+        }
+    }
+
+    // Unlinks one node at a time so long lists never recurse while being freed
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
 }
 
-#[test]
-fn push_pop_peek() {
-    let mut list = init_test_list();
+impl <T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
 
-    assert_eq!(list.peek(), Some(&1));
-    assert_eq!(list.pop(), Some(1));
-    assert_eq!(list.peek(), Some(&2));
-    assert_eq!(list.pop(), Some(2));
-    assert_eq!(list.peek(), Some(&3));
-    assert_eq!(list.pop(), Some(3));
+impl <T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
 }
 
-#[test]
-fn into_iter() {
-    let mut iter = init_test_list().into_iter();
+impl <T> Container for LinkedList<T> {
+    fn len(&self) -> usize {
+        self.length
+    }
 
-    assert_eq!(iter.next(), Some(1));
-    assert_eq!(iter.next(), Some(2));
-    assert_eq!(iter.next(), Some(3));
+    fn clear(&mut self) {
+        LinkedList::clear(self)
+    }
 }
 
-#[test]
-fn iter() {
-    let list = init_test_list();
-    let mut iter = list.iter();
+impl <T> Buffer<T> for LinkedList<T> {
+    fn push(&mut self, val: T) {
+        LinkedList::push(self, val)
+    }
 
-    assert_eq!(iter.next(), Some(&1));
-    assert_eq!(iter.next(), Some(&2));
-    assert_eq!(iter.next(), Some(&3));
+    fn pop(&mut self) -> Option<T> {
+        LinkedList::pop(self)
+    }
 
-    assert_eq!(list.peek(), Some(&1));
+    fn peek(&self) -> Option<&T> {
+        LinkedList::peek(self)
+    }
 }
 
-#[test]
-fn iter_mut() {
-    let mut list = init_test_list();
+impl <T> FifoBuffer<T> for LinkedList<T> {}
 
-    for val in list.iter_mut() {
-        *val *= 2;
+// ------------------------------ Sorting ------------------------------
+impl <T> LinkedList<T> {
+    pub fn sort(&mut self) where T: Ord {
+        self.sort_by(|a, b| a.cmp(b));
     }
 
-    assert_eq!(list.pop(), Some(2));
-    assert_eq!(list.pop(), Some(4));
-    assert_eq!(list.pop(), Some(6));
-}
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
 
-#[test]
-fn get() {
-    let list = init_test_list();
+    // Stable bottom-up merge sort, nodes are relinked in place so no values move and no
+    // extra memory is used. Runs of width 1, 2, 4, ... are merged over the next links only,
+    // the prev links and tail are rebuilt once sorting ends. Every node stays reachable from
+    // head the whole time, so a panicking compare still leaves a valid list behind
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        if self.length < 2 {
+            return;
+        }
 
-    assert_eq!(list.get(4), Some(&5));
-    assert_eq!(list.get(1), Some(&2));
-    assert_eq!(list.get(0), Some(&1));
-    assert_eq!(list.get(99), None);
-}
+        let guard = RelinkOnDrop(self);
+        let list = &mut *guard.0;
+        let next = |node: NodePointer<T>| unsafe { (*node.as_ptr()).next };
+        let mut width = 1;
 
-#[test]
-fn drop() {
-    let mut list = init_test_list();
+        loop {
+            let mut start = list.head;
+            // Last node of the merged output so far in this pass
+            let mut tail: Option<NodePointer<T>> = None;
+            let mut merges = 0;
 
-    assert_eq!(list.drop(1), Some(2));
-    assert_eq!(list.drop(1), Some(3));
-    assert_eq!(list.drop(1), Some(4));
-    assert_eq!(list.drop(4), None);
-    assert_eq!(list.drop(0), Some(1));
-}
+            while let Some(left_start) = start {
+                merges += 1;
+
+                let mut left_last = left_start;
+                let mut left_size = 1;
+
+                while left_size < width {
+                    let Some(node) = next(left_last) else { break };
+                    left_last = node;
+                    left_size += 1;
+                }
+
+                // The chain always runs tail -> rest of left run -> left_last -> rest of
+                // right run -> the remaining runs, right nodes are moved in front of left
+                let mut left = Some(left_start);
+                let mut right = next(left_last);
+                let mut right_size = width;
 
-fn main() {
-}
\ No newline at end of file
+                while left_size > 0 && right_size > 0 {
+                    let (Some(l), Some(r)) = (left, right) else { break };
+
+                    // Ties go to the left run to keep the sort stable
+                    if unsafe { compare(&(*l.as_ptr()).val, &(*r.as_ptr()).val) } != Ordering::Greater {
+                        tail = Some(l);
+                        left = next(l);
+                        left_size -= 1;
+                    } else {
+                        let after = next(r);
+
+                        unsafe {
+                            (*left_last.as_ptr()).next = after;
+                            (*r.as_ptr()).next = Some(l);
+
+                            match tail {
+                                Some(tail) => (*tail.as_ptr()).next = Some(r),
+                                None => list.head = Some(r)
+                            }
+                        }
+
+                        tail = Some(r);
+                        right = after;
+                        right_size -= 1;
+                    }
+                }
+
+                if left_size > 0 {
+                    tail = Some(left_last);
+                } else {
+                    while right_size > 0 {
+                        let Some(node) = right else { break };
+                        tail = Some(node);
+                        right = next(node);
+                        right_size -= 1;
+                    }
+                }
+
+                start = tail.and_then(next);
+            }
+
+            if merges <= 1 {
+                break;
+            }
+
+            width *= 2;
+        }
+    }
+
+    pub fn is_sorted(&self) -> bool where T: PartialOrd {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+
+    // Merges another sorted list into this sorted one, leaving other empty
+    pub fn merge_sorted(&mut self, other: &mut LinkedList<T>) where T: Ord {
+        self.merge_sorted_by(other, |a, b| a.cmp(b));
+    }
+
+    // Relinks the nodes of other into this list in O(n + m), values of this list come
+    // first when they compare equal
+    pub fn merge_sorted_by<F: FnMut(&T, &T) -> Ordering>(&mut self, other: &mut LinkedList<T>, mut compare: F) {
+        let mut other = mem::take(other);
+        let mut current = self.head;
+
+        while let Some(other_head) = other.head {
+            while let Some(node) = current {
+                let ordering = unsafe { compare(&(*node.as_ptr()).val, &(*other_head.as_ptr()).val) };
+
+                if ordering == Ordering::Greater {
+                    break;
+                }
+
+                current = unsafe { (*node.as_ptr()).next };
+            }
+
+            let Some(node) = current else {
+                self.append(&mut other);
+                break;
+            };
+
+            unsafe {
+                let moved = NonNull::from(Box::leak(other.unlink(other_head)));
+                self.link_between((*node.as_ptr()).prev, Some(node), moved);
+            }
+        }
+    }
+
+    // Rebuilds the prev links and tail from the next links
+    fn relink_prev(&mut self) {
+        let mut previous_node = None;
+        let mut current_node = self.head;
+
+        while let Some(node) = current_node {
+            unsafe {
+                (*node.as_ptr()).prev = previous_node;
+                current_node = (*node.as_ptr()).next;
+            }
+
+            previous_node = Some(node);
+        }
+
+        self.tail = previous_node;
+    }
+}
+
+// Rebuilds the prev links and tail when sorting ends, including by a panic in compare
+struct RelinkOnDrop<'a, T>(&'a mut LinkedList<T>);
+
+impl <T> Drop for RelinkOnDrop<'_, T> {
+    fn drop(&mut self) {
+        self.0.relink_prev();
+    }
+}
+
+// ------------------------------ Node Handles ------------------------------
+// Crate internal access to single nodes for structures that keep their own index into a
+// list. Every unsafe method needs a node that currently belongs to this list
+impl <T> LinkedList<T> {
+    pub(crate) fn push_front_node(&mut self, val: T) -> NodePointer<T> {
+        self.push_front(val);
+        self.head.expect("list is not empty after a push")
+    }
+
+    pub(crate) fn back_node(&self) -> Option<NodePointer<T>> {
+        self.tail
+    }
+
+    // Hands the head, tail and length over to the caller, who takes ownership of the nodes
+    pub(crate) fn into_nodes(self) -> (Option<NodePointer<T>>, Option<NodePointer<T>>, usize) {
+        let list = mem::ManuallyDrop::new(self);
+        (list.head, list.tail, list.length)
+    }
+
+    pub(crate) unsafe fn remove_node(&mut self, node: NodePointer<T>) -> T {
+        self.unlink(node).val
+    }
+
+    pub(crate) unsafe fn move_to_front(&mut self, node: NodePointer<T>) {
+        if self.head != Some(node) {
+            self.detach(node);
+            self.link_between(None, self.head, node);
+        }
+    }
+
+    pub(crate) unsafe fn node_val<'a>(node: NodePointer<T>) -> &'a T {
+        &(*node.as_ptr()).val
+    }
+
+    pub(crate) unsafe fn node_val_mut<'a>(node: NodePointer<T>) -> &'a mut T {
+        &mut (*node.as_ptr()).val
+    }
+}
+
+// ------------------------------ Algorithms ------------------------------
+impl <T> LinkedList<T> {
+    // Swaps every node's links, O(n) without moving any value
+    pub fn reverse(&mut self) {
+        let mut current_node = self.head;
+
+        while let Some(node) = current_node {
+            unsafe {
+                let node = node.as_ptr();
+                mem::swap(&mut (*node).prev, &mut (*node).next);
+                current_node = (*node).prev;
+            }
+        }
+
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    // Reverses every consecutive group of k values, a trailing group shorter than k is kept as is
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        if k < 2 {
+            return;
+        }
+
+        let mut new_head = None;
+        let mut previous_group_tail: Option<NodePointer<T>> = None;
+        let mut group_start = self.head;
+
+        loop {
+            let mut next_group_start = group_start;
+            let mut group_size = 0;
+
+            while group_size < k {
+                let Some(node) = next_group_start else { break };
+                next_group_start = unsafe { (*node.as_ptr()).next };
+                group_size += 1;
+            }
+
+            if group_size < k {
+                break;
+            }
+
+            // The group's first node ends up last, already pointing at the next group
+            let mut reversed = next_group_start;
+            let mut current_node = group_start;
+
+            for _ in 0..k {
+                let node = current_node.expect("group has k nodes");
+
+                unsafe {
+                    current_node = (*node.as_ptr()).next;
+                    (*node.as_ptr()).next = reversed;
+                }
+
+                reversed = Some(node);
+            }
+
+            match previous_group_tail {
+                Some(tail) => unsafe { (*tail.as_ptr()).next = reversed },
+                None => new_head = reversed
+            }
+
+            previous_group_tail = group_start;
+            group_start = next_group_start;
+        }
+
+        if new_head.is_some() {
+            self.head = new_head;
+            self.relink_prev();
+        }
+    }
+
+    // Moves the first n values to the back
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        let mut back = self.split_off(n % self.length).expect("index is within length");
+        back.append(self);
+        *self = back;
+    }
+
+    // Moves the last n values to the front
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        self.rotate_left(self.length - n % self.length);
+    }
+
+    // Removes consecutive repeated values, keeping the first of each run
+    pub fn dedup(&mut self) where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // same_bucket gets the kept value first and the candidate for removal second
+    pub fn dedup_by<F: FnMut(&T, &T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut kept = self.head;
+
+        while let Some(kept_node) = kept {
+            let Some(next_node) = (unsafe { (*kept_node.as_ptr()).next }) else { break };
+
+            if unsafe { same_bucket(&(*kept_node.as_ptr()).val, &(*next_node.as_ptr()).val) } {
+                unsafe { self.unlink(next_node) };
+            } else {
+                kept = Some(next_node);
+            }
+        }
+    }
+
+    // Keeps only the values the predicate returns true for
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) {
+        let mut current_node = self.head;
+
+        while let Some(node) = current_node {
+            unsafe {
+                current_node = (*node.as_ptr()).next;
+
+                if !predicate(&(*node.as_ptr()).val) {
+                    self.unlink(node);
+                }
+            }
+        }
+    }
+
+    // Slow/fast pointer walk, the second of the two middles for even lengths
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head?;
+        let mut fast = self.head;
+
+        while let Some(fast_node) = fast {
+            let Some(fast_next) = (unsafe { (*fast_node.as_ptr()).next }) else { break };
+
+            unsafe {
+                slow = (*slow.as_ptr()).next.expect("slow pointer trails the fast one");
+                fast = (*fast_next.as_ptr()).next;
+            }
+        }
+
+        Some(unsafe { &(*slow.as_ptr()).val })
+    }
+
+    // n = 0 is the last value, walks the prev links from the tail
+    pub fn nth_from_end(&self, n: usize) -> Option<&T> {
+        let mut current_node = self.tail?;
+
+        for _ in 0..n {
+            current_node = unsafe { (*current_node.as_ptr()).prev? };
+        }
+
+        Some(unsafe { &(*current_node.as_ptr()).val })
+    }
+
+    // Always false for a list that passes validate, useful as a sanity check
+    pub fn has_cycle(&self) -> bool {
+        unsafe { Node::has_cycle(self.head) }
+    }
+
+    pub fn cycle_start(&self) -> Option<&T> {
+        unsafe { Node::cycle_start(self.head).map(|node| &(*node.as_ptr()).val) }
+    }
+}
+
+// ------------------------------ Raw Nodes ------------------------------
+// Hand built chains of next links, which unlike a LinkedList are allowed to loop back on
+// themselves. The caller owns every node and must release the chain with free_chain
+impl <T> Node<T> {
+    pub fn new_raw(val: T) -> NonNull<Node<T>> {
+        Node::alloc(val)
+    }
+
+    /// # Safety
+    /// node has to come from new_raw and not be freed yet
+    pub unsafe fn set_next(node: NonNull<Node<T>>, next: Option<NonNull<Node<T>>>) {
+        (*node.as_ptr()).next = next;
+    }
+
+    /// # Safety
+    /// Every node reachable from head has to come from new_raw and not be freed yet
+    pub unsafe fn has_cycle(head: Option<NonNull<Node<T>>>) -> bool {
+        Node::meeting_point(head).is_some()
+    }
+
+    /// Floyd's algorithm: after the pointers meet, a pointer from the head and one from the
+    /// meeting point reach the start of the cycle after the same number of steps
+    ///
+    /// # Safety
+    /// Same as has_cycle
+    pub unsafe fn cycle_start(head: Option<NonNull<Node<T>>>) -> Option<NonNull<Node<T>>> {
+        let mut from_meeting = Node::meeting_point(head)?;
+        let mut from_head = head?;
+
+        while from_head != from_meeting {
+            from_head = (*from_head.as_ptr()).next?;
+            from_meeting = (*from_meeting.as_ptr()).next?;
+        }
+
+        Some(from_head)
+    }
+
+    /// Frees every node reachable from head, breaking the cycle first if there is one
+    ///
+    /// # Safety
+    /// Same as has_cycle, no node may be used afterwards
+    pub unsafe fn free_chain(head: Option<NonNull<Node<T>>>) {
+        if let Some(start) = Node::cycle_start(head) {
+            let mut last = start;
+
+            while let Some(next) = (*last.as_ptr()).next.filter(|&next| next != start) {
+                last = next;
+            }
+
+            (*last.as_ptr()).next = None;
+        }
+
+        let mut current_node = head;
+
+        while let Some(node) = current_node {
+            current_node = Box::from_raw(node.as_ptr()).next;
+        }
+    }
+
+    unsafe fn meeting_point(head: Option<NonNull<Node<T>>>) -> Option<NonNull<Node<T>>> {
+        let mut slow = head?;
+        let mut fast = head?;
+
+        loop {
+            fast = (*(*fast.as_ptr()).next?.as_ptr()).next?;
+            slow = (*slow.as_ptr()).next?;
+
+            if slow == fast {
+                return Some(slow);
+            }
+        }
+    }
+}
+
+// ------------------------------ Iterator ------------------------------
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl <T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl <T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.length, Some(self.0.length))
+    }
+}
+
+impl <T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl <T> ExactSizeIterator for IntoIter<T> {}
+
+// Both ends walk towards each other, remaining stops them once they meet
+pub struct Iter<'a, T> {
+    current_node: Option<NodePointer<T>>,
+    back_node: Option<NodePointer<T>>,
+    remaining: usize,
+    phantom: PhantomData<&'a T>
+}
+
+impl <'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl <'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.current_node.map(|node| unsafe {
+            self.remaining -= 1;
+            self.current_node = (*node.as_ptr()).next;
+            &(*node.as_ptr()).val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.back_node.map(|node| unsafe {
+            self.remaining -= 1;
+            self.back_node = (*node.as_ptr()).prev;
+            &(*node.as_ptr()).val
+        })
+    }
+}
+
+impl <T> ExactSizeIterator for Iter<'_, T> {}
+
+impl <T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+pub struct IterMut<'a, T> {
+    current_node: Option<NodePointer<T>>,
+    back_node: Option<NodePointer<T>>,
+    remaining: usize,
+    phantom: PhantomData<&'a mut T>
+}
+
+impl <'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl <'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.current_node.map(|node| unsafe {
+            self.remaining -= 1;
+            self.current_node = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.back_node.map(|node| unsafe {
+            self.remaining -= 1;
+            self.back_node = (*node.as_ptr()).prev;
+            &mut (*node.as_ptr()).val
+        })
+    }
+}
+
+impl <T> ExactSizeIterator for IterMut<'_, T> {}
+
+// ------------------------------ Traits ------------------------------
+impl <T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl <T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push_back(val);
+        }
+    }
+}
+
+impl <'a, T: Copy + 'a> Extend<&'a T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl <T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other)
+    }
+}
+
+impl <T: Eq> Eq for LinkedList<T> {}
+
+impl <T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl <T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl <T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.length.hash(state);
+
+        for val in self {
+            val.hash(state);
+        }
+    }
+}
+
+impl <T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+// The list owns its nodes the same way a Box would, so it can cross threads when T can
+unsafe impl <T: Send> Send for LinkedList<T> {}
+unsafe impl <T: Sync> Sync for LinkedList<T> {}
+
+// ------------------------------ Cursor ------------------------------
+// A cursor sits on a node or on the "ghost" position between the tail and the head,
+// where current is None and index equals the list's length
+pub struct Cursor<'a, T> {
+    index: usize,
+    current: Option<NodePointer<T>>,
+    list: &'a LinkedList<T>
+}
+
+impl <'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.length);
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.length.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head
+        };
+
+        next.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail
+        };
+
+        prev.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+}
+
+pub struct CursorMut<'a, T> {
+    index: usize,
+    current: Option<NodePointer<T>>,
+    list: &'a mut LinkedList<T>
+}
+
+impl <'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.length);
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.length.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head
+        };
+
+        next.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail
+        };
+
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    // Read only view of the list at the same position
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor { index: self.index, current: self.current, list: self.list }
+    }
+
+    // Inserts after the current node, or at the front when on the ghost position
+    pub fn insert_after(&mut self, val: T) {
+        let (prev, next) = self.after();
+        unsafe { self.list.link_between(prev, next, Node::alloc(val)) };
+
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    // Inserts before the current node, or at the back when on the ghost position
+    pub fn insert_before(&mut self, val: T) {
+        let (prev, next) = self.before();
+        unsafe { self.list.link_between(prev, next, Node::alloc(val)) };
+        self.index += 1;
+    }
+
+    // Removes the current node and moves on to the next one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        self.current = unsafe { (*node.as_ptr()).next };
+        Some(unsafe { self.list.unlink(node).val })
+    }
+
+    // Everything after the current node, or the whole list when on the ghost position
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        match self.current {
+            Some(node) => unsafe { self.list.split_after_node(node, self.index + 1) },
+            None => {
+                self.index = 0;
+                mem::take(self.list)
+            }
+        }
+    }
+
+    // Everything before the current node, or the whole list when on the ghost position
+    pub fn split_before(&mut self) -> LinkedList<T> {
+        let split_index = mem::replace(&mut self.index, 0);
+
+        match self.current {
+            Some(node) => unsafe { self.list.split_before_node(node, split_index) },
+            None => mem::take(self.list)
+        }
+    }
+
+    pub fn splice_after(&mut self, other: LinkedList<T>) {
+        let other_length = other.length;
+        let (prev, next) = self.after();
+        unsafe { self.list.splice_between(prev, next, other) };
+
+        if self.current.is_none() {
+            self.index += other_length;
+        }
+    }
+
+    pub fn splice_before(&mut self, other: LinkedList<T>) {
+        let other_length = other.length;
+        let (prev, next) = self.before();
+        unsafe { self.list.splice_between(prev, next, other) };
+        self.index += other_length;
+    }
+
+    fn after(&self) -> (Option<NodePointer<T>>, Option<NodePointer<T>>) {
+        match self.current {
+            Some(node) => (Some(node), unsafe { (*node.as_ptr()).next }),
+            None => (None, self.list.head)
+        }
+    }
+
+    fn before(&self) -> (Option<NodePointer<T>>, Option<NodePointer<T>>) {
+        match self.current {
+            Some(node) => (unsafe { (*node.as_ptr()).prev }, Some(node)),
+            None => (self.list.tail, None)
+        }
+    }
+}
+
+// Return list with 1, 2, 3
+fn init_test_list() -> LinkedList<i32> {
+    let mut list = LinkedList::<i32>::new();
+
+    list.push(1);
+    list.push(2);
+    list.push(3);
+    list.push(4);
+    list.push(5);
+    list.push(6);
+
+    list
+}
+
+#[test]
+fn push_pop_peek() {
+    let mut list = init_test_list();
+
+    assert_eq!(list.peek(), Some(&1));
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.peek(), Some(&2));
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.peek(), Some(&3));
+    assert_eq!(list.pop(), Some(3));
+}
+
+#[test]
+fn into_iter() {
+    let mut iter = init_test_list().into_iter();
+
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), Some(3));
+}
+
+#[test]
+fn iter() {
+    let list = init_test_list();
+    let mut iter = list.iter();
+
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+
+    assert_eq!(list.peek(), Some(&1));
+}
+
+#[test]
+fn iter_mut() {
+    let mut list = init_test_list();
+
+    for val in list.iter_mut() {
+        *val *= 2;
+    }
+
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.pop(), Some(4));
+    assert_eq!(list.pop(), Some(6));
+}
+
+#[test]
+fn get() {
+    let list = init_test_list();
+
+    assert_eq!(list.get(4), Some(&5));
+    assert_eq!(list.get(1), Some(&2));
+    assert_eq!(list.get(0), Some(&1));
+    assert_eq!(list.get(99), None);
+}
+
+#[test]
+fn drop() {
+    let mut list = init_test_list();
+
+    assert_eq!(list.drop(1), Some(2));
+    assert_eq!(list.drop(1), Some(3));
+    assert_eq!(list.drop(1), Some(4));
+    assert_eq!(list.drop(4), None);
+    assert_eq!(list.drop(0), Some(1));
+}
+
+#[test]
+fn try_insert_get_drop() {
+    let mut list = init_test_list();
+
+    assert_eq!(list.try_insert_at(99, 42), Err(DsaError::IndexOutOfBounds { index: 42, len: 6 }));
+    assert_eq!(list.try_insert_at(0, 0), Ok(()));
+    assert_eq!(list.try_get(0), Ok(&0));
+    assert_eq!(list.try_get(7), Err(DsaError::IndexOutOfBounds { index: 7, len: 7 }));
+    assert_eq!(list.try_drop(0), Ok(0));
+    assert_eq!(list.try_peek(), Ok(&1));
+
+    let mut empty = LinkedList::<i32>::new();
+    assert_eq!(empty.try_pop(), Err(DsaError::Empty));
+    assert_eq!(empty.try_peek(), Err(DsaError::Empty));
+}
+
+#[test]
+fn shared_borrows() {
+    let mut list = init_test_list();
+
+    // References outlive the calls that produced them and stay valid together
+    let first = list.peek().unwrap();
+    let refs: Vec<&i32> = list.iter().collect();
+    assert_eq!(list.get(5), Some(&6));
+    assert_eq!(first, refs[0]);
+
+    if let Some(val) = list.iter_mut().nth(2) {
+        *val = 30;
+    }
+
+    assert_eq!(list.get(2), Some(&30));
+}
+
+#[test]
+fn push_pop_both_ends() {
+    let mut list = LinkedList::<i32>::new();
+
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+
+    assert_eq!(list.peek(), Some(&1));
+    assert_eq!(list.peek_back(), Some(&3));
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.pop_front(), None);
+
+    list.push_front(4);
+    assert_eq!(list.peek_back(), Some(&4));
+}
+
+#[test]
+fn cursor() {
+    let list = init_test_list();
+    let mut cursor = list.cursor_front();
+
+    assert_eq!(cursor.current(), Some(&1));
+    assert_eq!(cursor.peek_prev(), None);
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(cursor.peek_next(), Some(&3));
+
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&6));
+
+    cursor.move_prev();
+    assert_eq!(cursor.index(), Some(5));
+    assert_eq!(list.cursor_back().current(), Some(&6));
+}
+
+#[test]
+fn cursor_mut_insert_remove() {
+    let mut list = init_test_list();
+    let mut cursor = list.cursor_front_mut();
+
+    cursor.move_next();
+    cursor.insert_before(10);
+    cursor.insert_after(20);
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.current(), Some(&mut 20));
+
+    if let Some(val) = cursor.current() {
+        *val = 21;
+    }
+
+    // Removing the tail leaves the cursor on the ghost position
+    let mut cursor = list.cursor_back_mut();
+    assert_eq!(cursor.remove_current(), Some(6));
+    assert_eq!(cursor.index(), None);
+    cursor.insert_after(0);
+    cursor.insert_before(7);
+
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 10, 21, 3, 4, 5, 7]);
+    assert_eq!(list.peek_back(), Some(&7));
+}
+
+#[test]
+fn cursor_mut_split_splice() {
+    let mut list = init_test_list();
+    let mut cursor = list.cursor_front_mut();
+
+    cursor.move_next();
+    cursor.move_next();
+    let mut back = cursor.split_after();
+    let front = cursor.split_before();
+    assert_eq!(cursor.index(), Some(0));
+
+    assert_eq!(front.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3]);
+    assert_eq!(list.peek_back(), Some(&3));
+
+    let mut cursor = back.cursor_front_mut();
+    cursor.splice_after(list);
+    cursor.splice_before(front);
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4, 3, 5, 6]);
+    assert_eq!(back.get(5), Some(&6));
+    assert_eq!(back.pop_back(), Some(6));
+}
+
+#[test]
+fn validate() {
+    let mut list = init_test_list();
+    assert_eq!(list.validate(), Ok(()));
+
+    // Emptying the list through pop has to clear the tail before the next push
+    while list.pop().is_some() {
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    assert_eq!(list.len(), 0);
+    list.push(1);
+    list.push(2);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.peek_back(), Some(&2));
+
+    // Dropping the last node has to move the tail back
+    assert_eq!(list.drop(1), Some(2));
+    assert_eq!(list.validate(), Ok(()));
+    list.push(3);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+
+    list.insert_at(2, 1);
+    list.insert_at(4, 3);
+    list.insert_at(0, 0);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.len(), 5);
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    let mut back = cursor.split_after();
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(back.validate(), Ok(()));
+
+    back.drop(0);
+    back.pop_back();
+    back.clear();
+    assert_eq!(back.validate(), Ok(()));
+    assert_eq!(back.len(), 0);
+}
+
+#[test]
+fn validate_reports_corruption() {
+    let mut list = init_test_list();
+
+    list.length = 4;
+    assert_eq!(list.validate(), Err(InvariantViolation::LengthMismatch { length: 4, nodes: 6 }));
+
+    list.length = 6;
+    let tail = list.tail;
+    list.tail = list.head;
+    assert_eq!(list.validate(), Err(InvariantViolation::TailMismatch));
+
+    list.tail = tail;
+    assert!(list.validate().is_ok());
+
+    // A cycle back into the list is caught at the first node visited twice
+    let last = list.node_at(5);
+    unsafe { (*last.as_ptr()).next = Some(list.node_at(2)) };
+    assert_eq!(list.validate(), Err(InvariantViolation::BrokenPrevLink { index: 6 }));
+
+    unsafe { (*last.as_ptr()).next = None };
+    assert!(list.validate().is_ok());
+}
+
+#[test]
+fn non_clone_values() {
+    let mut list = LinkedList::<Box<dyn Fn(i32) -> i32>>::new();
+
+    list.push(Box::new(|x| x + 1));
+    list.push(Box::new(|x| x * 2));
+    list.insert_at(Box::new(|x| x - 3), 1);
+
+    assert_eq!(list.iter().fold(10, |acc, f| f(acc)), 16);
+    assert_eq!(list.drop(1).map(|f| f(3)), Some(0));
+    assert_eq!(list.pop().map(|f| f(3)), Some(4));
+    assert_eq!(list.into_iter().map(|f| f(3)).collect::<Vec<_>>(), vec![6]);
+}
+
+#[test]
+fn clone() {
+    let list = init_test_list();
+    let mut copy = list.clone();
+
+    copy.push(7);
+    assert_eq!(copy.validate(), Ok(()));
+    assert_eq!(list.iter().count(), 6);
+    assert_eq!(copy.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn append_prepend() {
+    let mut list = init_test_list();
+    let mut other = init_test_list();
+
+    list.append(&mut other);
+    assert_eq!(other.len(), 0);
+    assert_eq!(other.validate(), Ok(()));
+    assert_eq!(list.len(), 12);
+    assert_eq!(list.peek_back(), Some(&6));
+
+    let mut other = LinkedList::new();
+    other.push(-1);
+    other.push(0);
+    list.prepend(&mut other);
+    list.append(&mut LinkedList::new());
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.iter().take(4).copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2]);
+
+    let mut empty = LinkedList::new();
+    empty.append(&mut list);
+    assert_eq!(empty.validate(), Ok(()));
+    assert_eq!(empty.len(), 14);
+}
+
+#[test]
+fn split_off() {
+    let mut list = init_test_list();
+
+    let back = list.split_off(4).unwrap();
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![5, 6]);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(back.validate(), Ok(()));
+
+    assert_eq!(list.split_off(4).map(|list| list.len()), Some(0));
+    assert_eq!(list.try_split_off(5).map(|list| list.len()), Err(DsaError::IndexOutOfBounds { index: 5, len: 4 }));
+
+    let all = list.split_off(0).unwrap();
+    assert_eq!(list.len(), 0);
+    assert_eq!(all.len(), 4);
+}
+
+#[test]
+fn splice() {
+    let mut list = init_test_list();
+    let mut other = LinkedList::new();
+    other.push(10);
+    other.push(20);
+
+    assert_eq!(list.try_splice(7, &mut other), Err(DsaError::IndexOutOfBounds { index: 7, len: 6 }));
+    assert_eq!(other.len(), 2);
+
+    list.splice(2, &mut other);
+    assert_eq!(other.len(), 0);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 10, 20, 3, 4, 5, 6]);
+
+    other.push(30);
+    list.splice(8, &mut other);
+    other.push(0);
+    list.splice(0, &mut other);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.peek(), Some(&0));
+    assert_eq!(list.peek_back(), Some(&30));
+}
+
+#[test]
+fn sort() {
+    let mut list = LinkedList::new();
+
+    for val in [5, -2, 9, 0, 5, 3, 11, -7, 2] {
+        list.push(val);
+    }
+
+    assert!(!list.is_sorted());
+    list.sort();
+    assert!(list.is_sorted());
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-7, -2, 0, 2, 3, 5, 5, 9, 11]);
+
+    list.sort_by(|a, b| b.cmp(a));
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.peek(), Some(&11));
+    assert_eq!(list.peek_back(), Some(&-7));
+
+    let mut empty = LinkedList::<i32>::new();
+    empty.sort();
+    assert!(empty.is_sorted());
+
+    // Odd lengths leave a short last run on every pass
+    let mut seed = 12345u32;
+    let mut values = Vec::new();
+    let mut long_list = LinkedList::new();
+
+    for _ in 0..1001 {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        values.push(seed % 100);
+        long_list.push(seed % 100);
+    }
+
+    values.sort();
+    long_list.sort();
+    assert_eq!(long_list.validate(), Ok(()));
+    assert_eq!(long_list.into_iter().collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn sort_is_stable() {
+    let mut list = LinkedList::new();
+
+    for (index, key) in [3, 1, 2, 1, 3, 2, 1].into_iter().enumerate() {
+        list.push((key, index));
+    }
+
+    list.sort_by_key(|&(key, _)| key);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        vec![(1, 1), (1, 3), (1, 6), (2, 2), (2, 5), (3, 0), (3, 4)]
+    );
+}
+
+#[test]
+fn sort_survives_panicking_compare() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let words: Vec<String> = (0..37).map(|i| format!("word{}", (i * 17) % 37)).collect();
+
+    // Panic at every possible point of the sort until one run gets to finish
+    for panic_at in 0.. {
+        let mut list: LinkedList<String> = words.iter().cloned().collect();
+        let mut calls = 0;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                calls += 1;
+
+                if calls > panic_at {
+                    panic!("compare gave up");
+                }
+
+                a.cmp(b)
+            })
+        }));
+
+        assert_eq!(list.validate(), Ok(()));
+
+        let mut values: Vec<String> = list.iter().cloned().collect();
+        values.sort();
+        let mut expected = words.clone();
+        expected.sort();
+        assert_eq!(values, expected);
+
+        if result.is_ok() {
+            assert!(list.is_sorted());
+            break;
+        }
+    }
+}
+
+#[test]
+fn merge_sorted() {
+    let mut list = LinkedList::new();
+    let mut other = LinkedList::new();
+
+    for val in [1, 4, 4, 9] {
+        list.push((val, 'a'));
+    }
+
+    for val in [0, 4, 10, 12] {
+        other.push((val, 'b'));
+    }
+
+    list.merge_sorted_by(&mut other, |a, b| a.0.cmp(&b.0));
+    assert_eq!(other.len(), 0);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        vec![(0, 'b'), (1, 'a'), (4, 'a'), (4, 'a'), (4, 'b'), (9, 'a'), (10, 'b'), (12, 'b')]
+    );
+
+    let mut numbers = init_test_list();
+    let mut more = init_test_list();
+    numbers.merge_sorted(&mut more);
+    assert!(numbers.is_sorted());
+    assert_eq!(numbers.len(), 12);
+}
+
+fn list_of(values: &[i32]) -> LinkedList<i32> {
+    let mut list = LinkedList::new();
+
+    for &val in values {
+        list.push(val);
+    }
+
+    list
+}
+
+fn values_of(list: &LinkedList<i32>) -> Vec<i32> {
+    list.iter().copied().collect()
+}
+
+#[test]
+fn reverse() {
+    let mut list = init_test_list();
+
+    list.reverse();
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![6, 5, 4, 3, 2, 1]);
+
+    let mut single = list_of(&[1]);
+    single.reverse();
+    assert_eq!(single.validate(), Ok(()));
+}
+
+#[test]
+fn reverse_in_groups() {
+    let mut list = init_test_list();
+    list.reverse_in_groups(2);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![2, 1, 4, 3, 6, 5]);
+
+    let mut list = init_test_list();
+    list.reverse_in_groups(4);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![4, 3, 2, 1, 5, 6]);
+
+    let mut list = init_test_list();
+    list.reverse_in_groups(7);
+    list.reverse_in_groups(1);
+    assert_eq!(values_of(&list), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn rotate() {
+    let mut list = init_test_list();
+
+    list.rotate_left(2);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![3, 4, 5, 6, 1, 2]);
+
+    list.rotate_right(8);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![1, 2, 3, 4, 5, 6]);
+
+    list.rotate_left(6);
+    assert_eq!(values_of(&list), vec![1, 2, 3, 4, 5, 6]);
+
+    let mut empty = LinkedList::<i32>::new();
+    empty.rotate_left(3);
+    empty.rotate_right(3);
+}
+
+#[test]
+fn dedup_retain() {
+    let mut list = list_of(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    list.dedup();
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![1, 2, 3, 1, 4]);
+
+    let mut list = list_of(&[10, 11, 20, 25, 31, 5]);
+    list.dedup_by_key(|val| val / 10);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![10, 20, 31, 5]);
+
+    list.retain(|val| val % 2 == 1);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(values_of(&list), vec![31, 5]);
+
+    list.retain(|_| false);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn middle_nth_from_end() {
+    assert_eq!(list_of(&[1, 2, 3, 4, 5]).middle(), Some(&3));
+    assert_eq!(init_test_list().middle(), Some(&4));
+    assert_eq!(list_of(&[1]).middle(), Some(&1));
+    assert_eq!(LinkedList::<i32>::new().middle(), None);
+
+    let list = init_test_list();
+    assert_eq!(list.nth_from_end(0), Some(&6));
+    assert_eq!(list.nth_from_end(5), Some(&1));
+    assert_eq!(list.nth_from_end(6), None);
+}
+
+#[test]
+fn cycle_detection() {
+    let list = init_test_list();
+    assert!(!list.has_cycle());
+    assert_eq!(list.cycle_start(), None);
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+    let nodes: Vec<_> = (0..5).map(Node::new_raw).collect();
+
+    unsafe {
+        for pair in nodes.windows(2) {
+            Node::set_next(pair[0], Some(pair[1]));
+        }
+
+        assert!(!Node::has_cycle(Some(nodes[0])));
+        assert_eq!(Node::cycle_start(Some(nodes[0])), None);
+
+        Node::set_next(nodes[4], Some(nodes[2]));
+        assert!(Node::has_cycle(Some(nodes[0])));
+        assert_eq!(Node::cycle_start(Some(nodes[0])), Some(nodes[2]));
+        assert_eq!(Node::cycle_start(Some(nodes[3])), Some(nodes[3]));
+
+        Node::free_chain(Some(nodes[0]));
+    }
+}
+
+#[test]
+fn collect_extend() {
+    let mut list: LinkedList<i32> = (1..=3).collect();
+
+    list.extend(vec![4, 5]);
+    list.extend(&[6]);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list, init_test_list());
+
+    let mut sum = 0;
+
+    for val in &list {
+        sum += val;
+    }
+
+    for val in &mut list {
+        *val += 1;
+    }
+
+    assert_eq!(sum, 21);
+    assert_eq!(list.iter().collect::<LinkedList<_>>(), list.iter().collect::<LinkedList<_>>());
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn double_ended_exact_size() {
+    let mut list = init_test_list();
+
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&6));
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.rev().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2]);
+
+    let mut iter_mut = list.iter_mut();
+    *iter_mut.next_back().unwrap() = 60;
+    *iter_mut.next().unwrap() = 10;
+    assert_eq!(iter_mut.len(), 4);
+    // The two ends must not hand out the same node twice
+    assert_eq!(iter_mut.count(), 4);
+
+    let mut into_iter = list.into_iter();
+    assert_eq!(into_iter.next_back(), Some(60));
+    assert_eq!(into_iter.len(), 5);
+    assert_eq!(into_iter.next(), Some(10));
+}
+
+#[test]
+fn comparison_hash_debug() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let hash = |list: &LinkedList<i32>| {
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let list = init_test_list();
+    let shorter = list_of(&[1, 2, 3]);
+    let bigger = list_of(&[1, 3]);
+
+    assert_eq!(list, list.clone());
+    assert_ne!(list, shorter);
+    assert!(shorter < list);
+    assert!(list < bigger);
+    assert_eq!(list.cmp(&list.clone()), Ordering::Equal);
+    assert_eq!(hash(&list), hash(&init_test_list()));
+    assert_ne!(hash(&list), hash(&shorter));
+
+    assert_eq!(format!("{:?}", shorter), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+}
+
+#[test]
+fn display() {
+    let list = init_test_list();
+
+    assert_eq!(list.to_string(), "1 -> 2 -> 3 -> 4 -> 5 -> 6\nLength - 6");
+    assert_eq!(LinkedList::<i32>::new().to_string(), "\nLength - 0");
+    assert_eq!(
+        list.display().separator(", ").brackets("[", "]").show_len(false).to_string(),
+        "[1, 2, 3, 4, 5, 6]"
+    );
+    assert_eq!(
+        list.display().separator(", ").brackets("[", "]").max_items(2).to_string(),
+        "[1, 2, ...]\nLength - 6"
+    );
+    assert_eq!(list.display().max_items(0).show_len(false).to_string(), "...");
+    assert_eq!(list.display().max_items(6).show_len(false).to_string(), "1 -> 2 -> 3 -> 4 -> 5 -> 6");
+}
+
+#[test]
+fn display_alternate() {
+    let list = list_of(&[7, 8, 9]);
+
+    assert_eq!(format!("{:#}", list), "0: 7\n1: 8\n2: 9\nLength - 3");
+    assert_eq!(
+        format!("{:#}", list.display().brackets("[", "]").max_items(1).show_len(false)),
+        "[\n0: 7\n...\n]"
+    );
+    assert_eq!(format!("{:#}", list.display().brackets("[", "]")), "[\n0: 7\n1: 8\n2: 9\n]\nLength - 3");
+    assert_eq!(format!("{:#}", list.display().show_len(false)), "0: 7\n1: 8\n2: 9");
+    assert_eq!(format!("{:#}", LinkedList::<i32>::new().display().show_len(false)), "");
+}
+
+#[test]
+fn drop_long_list() {
+    let mut list: LinkedList<usize> = (0..5_000_000).collect();
+
+    list.clear();
+    assert!(list.is_empty());
+    assert_eq!(list.validate(), Ok(()));
+
+    list.extend(0..5_000_000);
+    std::mem::drop(list);
+}
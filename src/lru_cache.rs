@@ -0,0 +1,275 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{linked_list::{LinkedList, NodePointer}, traits::Container};
+
+// ------------------------------ Stats ------------------------------
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64
+}
+
+impl CacheStats {
+    // Share of lookups that were hits, 0 before any lookup
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+// ------------------------------ LRU Cache ------------------------------
+// Entries live in a doubly linked list ordered from most to least recently used, the map
+// points every key at its node so lookups, reordering and eviction are all O(1)
+pub struct LruCache<K, V> {
+    list: LinkedList<(K, V)>,
+    map: HashMap<K, NodePointer<(K, V)>>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<Box<dyn FnMut(K, V)>>
+}
+
+impl <K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            list: LinkedList::new(),
+            map: HashMap::with_capacity(capacity),
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None
+        }
+    }
+
+    // Called with every entry pushed out because the cache ran out of room
+    pub fn on_evict<F: FnMut(K, V) + 'static>(&mut self, callback: F) {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    // Marks the entry as most recently used and counts a hit or a miss
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.touch(key).map(|node| unsafe { &LinkedList::node_val(node).1 })
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.touch(key).map(|node| unsafe { &mut LinkedList::node_val_mut(node).1 })
+    }
+
+    // Looks the entry up without changing its position or the stats
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|&node| unsafe { &LinkedList::node_val(node).1 })
+    }
+
+    // Inserts or replaces an entry as the most recently used one, returning the old value
+    pub fn put(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(&node) = self.map.get(&key) {
+            unsafe {
+                self.list.move_to_front(node);
+                return Some(std::mem::replace(&mut LinkedList::node_val_mut(node).1, val));
+            }
+        }
+
+        if self.capacity == 0 {
+            if let Some(callback) = self.on_evict.as_mut() {
+                callback(key, val);
+            }
+
+            return None;
+        }
+
+        if self.list.len() == self.capacity {
+            self.evict();
+        }
+
+        let node = self.list.push_front_node((key.clone(), val));
+        self.map.insert(key, node);
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.map.remove(key)?;
+        Some(unsafe { self.list.remove_node(node).1 })
+    }
+
+    // Removes the least recently used entry without calling the eviction callback
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let node = self.list.back_node()?;
+        let (key, val) = unsafe { self.list.remove_node(node) };
+        self.map.remove(&key);
+        Some((key, val))
+    }
+
+    // Shrinking evicts least recently used entries until the cache fits
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+
+        while self.list.len() > self.capacity {
+            self.evict();
+        }
+    }
+
+    // Entries from the most to the least recently used
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.list.iter().map(|(key, val)| (key, val))
+    }
+
+    fn touch(&mut self, key: &K) -> Option<NodePointer<(K, V)>> {
+        match self.map.get(key) {
+            Some(&node) => {
+                self.stats.hits += 1;
+                unsafe { self.list.move_to_front(node) };
+                Some(node)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn evict(&mut self) {
+        if let Some((key, val)) = self.pop_lru() {
+            if let Some(callback) = self.on_evict.as_mut() {
+                callback(key, val);
+            }
+        }
+    }
+}
+
+impl <K: Hash + Eq + Clone, V> Container for LruCache<K, V> {
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.list.clear();
+    }
+}
+
+#[cfg(test)]
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn get_put() {
+    let mut cache = LruCache::new(2);
+
+    assert_eq!(cache.put("a", 1), None);
+    assert_eq!(cache.put("b", 2), None);
+    assert_eq!(cache.get(&"a"), Some(&1));
+
+    // b is now the least recently used entry
+    cache.put("c", 3);
+    assert!(!cache.contains(&"b"));
+    assert_eq!(cache.get(&"b"), None);
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(cache.put("a", 10), Some(1));
+    cache.put("d", 4);
+    assert_eq!(cache.peek(&"a"), Some(&10));
+    assert_eq!(cache.peek(&"c"), None);
+
+    if let Some(val) = cache.get_mut(&"d") {
+        *val += 1;
+    }
+
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"d", &5), (&"a", &10)]);
+    assert_eq!(cache.list.validate(), Ok(()));
+}
+
+#[test]
+fn peek_does_not_reorder() {
+    let mut cache = LruCache::new(2);
+
+    cache.put(1, "one");
+    cache.put(2, "two");
+    assert_eq!(cache.peek(&1), Some(&"one"));
+
+    cache.put(3, "three");
+    assert_eq!(cache.peek(&1), None);
+    assert_eq!(cache.stats(), CacheStats::default());
+}
+
+#[test]
+fn pop_lru_remove() {
+    let mut cache = LruCache::new(3);
+
+    cache.put(1, 'a');
+    cache.put(2, 'b');
+    cache.put(3, 'c');
+    cache.get(&1);
+
+    assert_eq!(cache.pop_lru(), Some((2, 'b')));
+    assert_eq!(cache.remove(&3), Some('c'));
+    assert_eq!(cache.remove(&3), None);
+    assert_eq!(cache.pop_lru(), Some((1, 'a')));
+    assert_eq!(cache.pop_lru(), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn eviction_callback_resize() {
+    let evicted = Rc::new(RefCell::new(Vec::new()));
+    let mut cache = LruCache::new(3);
+
+    let log = evicted.clone();
+    cache.on_evict(move |key, val| log.borrow_mut().push((key, val)));
+
+    for key in 0..5 {
+        cache.put(key, key * 10);
+    }
+
+    assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10)]);
+
+    cache.resize(1);
+    assert_eq!(cache.capacity(), 1);
+    assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10), (2, 20), (3, 30)]);
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&4, &40)]);
+
+    cache.resize(0);
+    cache.put(9, 90);
+    assert!(cache.is_empty());
+    assert_eq!(evicted.borrow()[4..], [(4, 40), (9, 90)]);
+
+    // Explicit removals are not evictions
+    cache.resize(2);
+    cache.put(5, 50);
+    cache.pop_lru();
+    cache.clear();
+    assert_eq!(evicted.borrow().len(), 6);
+}
+
+#[test]
+fn stats() {
+    let mut cache = LruCache::new(2);
+
+    cache.put("x", 1);
+    cache.get(&"x");
+    cache.get(&"x");
+    cache.get(&"y");
+
+    assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 1 });
+    assert!((cache.stats().hit_ratio() - 2.0 / 3.0).abs() < 1e-9);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats().hit_ratio(), 0.0);
+}
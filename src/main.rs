@@ -1,8 +1,28 @@
-// mod stack;
-// mod linked_list;
-// mod queue;
-// mod priority_queue;
-mod union_find;
+use dsa::{Array, LinkedList, Stack};
 
 fn main() {
-}
\ No newline at end of file
+    let mut array = Array::<i32>::new(2);
+    array.add(0);
+    array.add(1);
+    array.add(2);
+    array.add(3);
+
+    match array.remove() {
+        Ok(()) => (),
+        Err(e) => println!("{}", e)
+    };
+    array.add(4);
+
+    println!("{:?}, {}", array.arr, array.index(1));
+
+    let mut stack = Stack::new();
+    let mut list = LinkedList::new();
+
+    for val in 1..=5 {
+        stack.push(val);
+        list.push(val);
+    }
+
+    println!("Stack top - {:?}", stack.peek());
+    println!("{}", list);
+}
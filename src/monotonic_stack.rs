@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+
+use crate::stack::Stack;
+
+// ------------------------------ Nearest Index ------------------------------
+// Visits indexes in order and answers, for each one, the closest index visited before it
+// whose value qualifies against it. Indexes that fail to qualify are popped for good: the
+// current index is closer and beats them, so they can't be the answer for any later one.
+// Every index is pushed and popped at most once, which makes the whole pass O(n)
+fn nearest<T, I, F>(values: &[T], order: I, mut qualifies: F) -> Vec<Option<usize>>
+where
+    I: Iterator<Item = usize>,
+    F: FnMut(&T, &T) -> bool
+{
+    let mut result = vec![None; values.len()];
+    let mut candidates: Stack<usize> = Stack::new();
+
+    for i in order {
+        while let Some(&j) = candidates.peek() {
+            if qualifies(&values[j], &values[i]) {
+                break;
+            }
+
+            candidates.pop();
+        }
+
+        result[i] = candidates.peek().copied();
+        candidates.push(i);
+    }
+
+    result
+}
+
+// Index of the first strictly greater value to the right of each value
+pub fn next_greater<T: Ord>(values: &[T]) -> Vec<Option<usize>> {
+    next_greater_by(values, T::cmp)
+}
+
+pub fn next_greater_by<T, F: FnMut(&T, &T) -> Ordering>(values: &[T], mut compare: F) -> Vec<Option<usize>> {
+    nearest(values, (0..values.len()).rev(), |candidate, val| compare(candidate, val) == Ordering::Greater)
+}
+
+// Index of the last strictly smaller value to the left of each value
+pub fn previous_smaller<T: Ord>(values: &[T]) -> Vec<Option<usize>> {
+    previous_smaller_by(values, T::cmp)
+}
+
+pub fn previous_smaller_by<T, F: FnMut(&T, &T) -> Ordering>(values: &[T], mut compare: F) -> Vec<Option<usize>> {
+    nearest(values, 0..values.len(), |candidate, val| compare(candidate, val) == Ordering::Less)
+}
+
+// ------------------------------ Areas ------------------------------
+// Heights here are amounts, not just ordered keys, since areas and volumes multiply them
+// with widths. That's why these take Into<u64> heights instead of a comparator. Results are
+// u128 so a height up to u64::MAX times any slice length can't overflow
+
+// Every bar is the lowest of the widest rectangle reaching out to the nearest lower bars
+pub fn largest_rectangle_in_histogram<T: Copy + Ord + Into<u64>>(heights: &[T]) -> u128 {
+    let left = previous_smaller(heights);
+    let right = nearest(heights, (0..heights.len()).rev(), |candidate, height| candidate < height);
+
+    (0..heights.len())
+        .map(|i| {
+            let width = right[i].unwrap_or(heights.len()) - left[i].map_or(0, |l| l + 1);
+            u128::from(heights[i].into()) * width as u128
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Area of the largest all true rectangle. Each row turns into a histogram of how many true
+// cells are stacked above and on it, missing cells of short rows count as false
+pub fn maximal_rectangle<R: AsRef<[bool]>>(matrix: &[R]) -> u128 {
+    let width = matrix.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+    let mut heights = vec![0u64; width];
+    let mut largest = 0;
+
+    for row in matrix {
+        let row = row.as_ref();
+
+        for (j, height) in heights.iter_mut().enumerate() {
+            *height = if row.get(j) == Some(&true) { *height + 1 } else { 0 };
+        }
+
+        largest = largest.max(largest_rectangle_in_histogram(&heights));
+    }
+
+    largest
+}
+
+// Water held between the bars after rain. The stack keeps falling bars, a taller bar fills
+// the basin between itself and the bar left of the popped one layer by layer
+pub fn trapping_rain_water<T: Copy + Ord + Into<u64>>(heights: &[T]) -> u128 {
+    let mut walls: Stack<usize> = Stack::new();
+    let mut water = 0;
+
+    for (i, &height) in heights.iter().enumerate() {
+        while let Some(&bottom) = walls.peek() {
+            if heights[bottom] >= height {
+                break;
+            }
+
+            walls.pop();
+
+            let Some(&left) = walls.peek() else { break };
+            let level = heights[left].min(height).into() - heights[bottom].into();
+            water += u128::from(level) * (i - left - 1) as u128;
+        }
+
+        walls.push(i);
+    }
+
+    water
+}
+
+// ------------------------------ Subarrays ------------------------------
+// Sum over every contiguous subarray of its minimum. Each value is the minimum of the
+// subarrays reaching to the nearest smaller values, ties go to the leftmost copy so no
+// subarray is counted twice
+pub fn sum_of_subarray_minimums<T: Copy + Ord + Into<i128>>(values: &[T]) -> i128 {
+    let left = previous_smaller(values);
+    let right = nearest(values, (0..values.len()).rev(), |candidate, val| candidate <= val);
+
+    (0..values.len())
+        .map(|i| {
+            let starts = i - left[i].map_or(0, |l| l + 1) + 1;
+            let ends = right[i].unwrap_or(values.len()) - i;
+            values[i].into() * (starts * ends) as i128
+        })
+        .sum()
+}
+
+// ------------------------------ Stock Span ------------------------------
+// Fed one price at a time, reports how many consecutive prices up to and including the
+// latest were at most that price. Prices swallowed by a higher one are kept only as a count
+pub struct StockSpan<T> {
+    prices: Stack<(T, usize)>
+}
+
+impl <T: Ord> Default for StockSpan<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T: Ord> StockSpan<T> {
+    pub fn new() -> Self {
+        StockSpan { prices: Stack::new() }
+    }
+
+    pub fn next(&mut self, price: T) -> usize {
+        let mut span = 1;
+
+        while self.prices.peek().is_some_and(|(top, _)| *top <= price) {
+            span += self.prices.pop().expect("peeked a price").1;
+        }
+
+        self.prices.push((price, span));
+        span
+    }
+}
+
+#[test]
+fn nearest_indexes() {
+    assert_eq!(next_greater(&[2, 1, 2, 4, 3]), vec![Some(3), Some(2), Some(3), None, None]);
+    assert_eq!(previous_smaller(&[3, 1, 2, 4, 2]), vec![None, None, Some(1), Some(2), Some(1)]);
+    assert_eq!(next_greater::<i32>(&[]), vec![]);
+
+    let words = ["ccc", "a", "bb", "dddd"];
+    assert_eq!(next_greater_by(&words, |a, b| a.len().cmp(&b.len())), vec![Some(3), Some(2), Some(3), None]);
+
+    // A reversed comparator turns the search around
+    assert_eq!(previous_smaller_by(&[3, 1, 2, 4, 2], |a, b| b.cmp(a)), vec![None, Some(0), Some(0), None, Some(3)]);
+}
+
+#[test]
+fn histogram_and_matrix() {
+    assert_eq!(largest_rectangle_in_histogram(&[2u32, 1, 5, 6, 2, 3]), 10);
+    assert_eq!(largest_rectangle_in_histogram(&[2u8, 2, 2]), 6);
+    assert_eq!(largest_rectangle_in_histogram::<u64>(&[]), 0);
+
+    let matrix = [
+        [true, false, true, false, false],
+        [true, false, true, true, true],
+        [true, true, true, true, true],
+        [true, false, false, true, false]
+    ];
+    assert_eq!(maximal_rectangle(&matrix), 6);
+    assert_eq!(maximal_rectangle(&[vec![true, true], vec![true]]), 2);
+    assert_eq!(maximal_rectangle::<Vec<bool>>(&[]), 0);
+}
+
+#[test]
+fn rain_water() {
+    assert_eq!(trapping_rain_water(&[0u32, 1, 0, 2, 1, 0, 1, 3, 2, 1, 2, 1]), 6);
+    assert_eq!(trapping_rain_water(&[4u64, 2, 0, 3, 2, 5]), 9);
+    assert_eq!(trapping_rain_water(&[1u8, 2, 3]), 0);
+}
+
+#[test]
+fn large_heights() {
+    let max = u64::MAX as u128;
+
+    assert_eq!(largest_rectangle_in_histogram(&[u64::MAX, u64::MAX]), 2 * max);
+    assert_eq!(largest_rectangle_in_histogram(&[u64::MAX, 1, u64::MAX]), max);
+    assert_eq!(trapping_rain_water(&[u64::MAX, 0, 0, u64::MAX]), 2 * max);
+    assert_eq!(trapping_rain_water(&[u64::MAX, 0, u64::MAX, 0, u64::MAX]), 2 * max);
+}
+
+#[test]
+fn subarray_minimums() {
+    assert_eq!(sum_of_subarray_minimums(&[3, 1, 2, 4]), 17);
+    assert_eq!(sum_of_subarray_minimums(&[11, 81, 94, 43, 3]), 444);
+    assert_eq!(sum_of_subarray_minimums(&[2i64, 2, -1]), 2 + 2 + 2 - 1 - 1 - 1);
+}
+
+#[test]
+fn stock_span() {
+    let mut spans = StockSpan::new();
+    let prices = [100, 80, 60, 70, 60, 75, 85];
+
+    assert_eq!(prices.map(|price| spans.next(price)), [1, 1, 1, 2, 1, 4, 6]);
+}
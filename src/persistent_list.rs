@@ -0,0 +1,207 @@
+use std::{fmt::{self, Debug}, rc::Rc};
+
+use crate::traits::Container;
+
+// ------------------------------ Node ------------------------------
+struct Node<T> {
+    val: T,
+    next: Option<Rc<Node<T>>>
+}
+
+// ------------------------------ Persistent List ------------------------------
+// Immutable singly linked list, every version is a pointer to its first node. cons and tail
+// build new versions on top of the old nodes, so snapshots share their common suffix and
+// cloning a list is O(1)
+pub struct PersistentList<T> {
+    head: Option<Rc<Node<T>>>,
+    length: usize
+}
+
+impl <T> Default for PersistentList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> PersistentList<T> {
+    pub fn new() -> Self {
+        PersistentList { head: None, length: 0 }
+    }
+
+    // New version with val in front of this one
+    pub fn cons(&self, val: T) -> Self {
+        PersistentList {
+            head: Some(Rc::new(Node { val, next: self.head.clone() })),
+            length: self.length + 1
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.val)
+    }
+
+    // Version without the first value, the tail of an empty list is empty
+    pub fn tail(&self) -> Self {
+        match &self.head {
+            Some(node) => PersistentList { head: node.next.clone(), length: self.length - 1 },
+            None => PersistentList::new()
+        }
+    }
+
+    // True when both versions start at the very same node
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref(), remaining: self.length }
+    }
+}
+
+impl <T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        PersistentList { head: self.head.clone(), length: self.length }
+    }
+}
+
+// Frees nodes in a loop instead of recursing through next, stopping at the first node
+// another version still holds
+impl <T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+
+        while let Some(node) = current {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => current = node.next.take(),
+                Err(_) => break
+            }
+        }
+    }
+}
+
+impl <T> Container for PersistentList<T> {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    // Only drops this version, nodes shared with other versions stay alive
+    fn clear(&mut self) {
+        *self = PersistentList::new();
+    }
+}
+
+// Values keep their iteration order, the first one becomes the head
+impl <T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let vals: Vec<T> = iter.into_iter().collect();
+        let mut list = PersistentList::new();
+
+        for val in vals.into_iter().rev() {
+            list = list.cons(val);
+        }
+
+        list
+    }
+}
+
+impl <T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: Eq> Eq for PersistentList<T> {}
+
+impl <T: Debug> Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// ------------------------------ Iterator ------------------------------
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    remaining: usize
+}
+
+impl <'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.remaining -= 1;
+            self.next = node.next.as_deref();
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> ExactSizeIterator for Iter<'_, T> {}
+
+impl <'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn cons_head_tail() {
+    let empty = PersistentList::new();
+    let list = empty.cons(3).cons(2).cons(1);
+
+    assert_eq!(list.head(), Some(&1));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.tail().head(), Some(&2));
+    assert_eq!(list.tail().tail().tail(), empty);
+    assert_eq!(empty.tail().len(), 0);
+    assert_eq!(empty.head(), None);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(list, (1..=3).collect());
+}
+
+#[test]
+fn versions_share_suffixes() {
+    let base: PersistentList<&str> = ["timeout=30", "retries=3"].into_iter().collect();
+    let a = base.cons("port=80");
+    let b = base.cons("port=443");
+
+    assert!(a.tail().ptr_eq(&base));
+    assert!(b.tail().ptr_eq(&a.tail()));
+    assert!(!a.ptr_eq(&b));
+    assert_eq!(format!("{:?}", b), r#"["port=443", "timeout=30", "retries=3"]"#);
+
+    // Dropping one version leaves the shared nodes to the others
+    drop(a);
+    drop(base);
+    assert_eq!(b.tail().head(), Some(&"timeout=30"));
+    assert_eq!(b.len(), 3);
+}
+
+#[test]
+fn drop_long_list() {
+    let mut list = PersistentList::new();
+
+    for val in 0..1_000_000 {
+        list = list.cons(val);
+    }
+
+    let snapshot = list.tail().tail();
+    drop(list);
+    assert_eq!(snapshot.head(), Some(&999_997));
+
+    let mut cleared = snapshot.clone();
+    cleared.clear();
+    assert!(cleared.is_empty());
+    assert_eq!(snapshot.len(), 999_998);
+}
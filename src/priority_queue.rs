@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
-use std::{collections::{BTreeSet, HashMap}, fmt::Debug, hash::Hash};
+use std::{collections::{BTreeSet, HashMap}, hash::Hash};
+
+use crate::{error::DsaError, traits::{Buffer, Container, PriorityBuffer}};
 
 pub enum Sort {
     Min, 
@@ -14,7 +16,7 @@ pub struct PriorityQueue<T> {
     map: HashMap<T, BTreeSet<usize>>
 }
 
-impl <T: Ord + Debug + Hash + Clone> PriorityQueue<T> {
+impl <T: Ord + Hash + Clone> PriorityQueue<T> {
     pub fn new(sort: Sort) -> Self {
         PriorityQueue {
             heap: vec![],
@@ -25,21 +27,20 @@ impl <T: Ord + Debug + Hash + Clone> PriorityQueue<T> {
     }
 
     pub fn add(&mut self, elem: T) {
-        self.map.entry(elem.clone()).or_insert_with(BTreeSet::new).insert(self.heap_size);
+        self.map.entry(elem.clone()).or_default().insert(self.heap_size);
         self.heap.push(elem);
 
         self.swim_up(self.heap_size);
         self.heap_size += 1;
     }
 
-    // true if i is in the valid sorting order to j, otherwise false
+    // true if i is in the valid sorting order to j, otherwise false. Missing indexes are
+    // never in order, whichever way the heap sorts
     fn compare(&self, i: usize, j: usize) -> bool {
-        let output = self.heap.get(i).zip(self.heap.get(j)).map_or(false, |(a, b)| a <= b);
-
-        match self.sort {
-            Sort::Min => output,
-            Sort::Max => !output
-        }
+        self.heap.get(i).zip(self.heap.get(j)).is_some_and(|(a, b)| match self.sort {
+            Sort::Min => a <= b,
+            Sort::Max => a >= b
+        })
     }
 
     fn swap(&mut self, i: usize, j: usize) {
@@ -97,34 +98,62 @@ impl <T: Ord + Debug + Hash + Clone> PriorityQueue<T> {
         self.heap_size
     }
 
-    pub fn poll(&mut self) -> T {
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.first()
+    }
+
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
+    pub fn poll(&mut self) -> Option<T> {
+        self.try_poll().ok()
+    }
+
+    pub fn try_poll(&mut self) -> Result<T, DsaError> {
+        if self.heap_size == 0 {
+            return Err(DsaError::Empty);
+        }
+
         self.swap(0, self.heap_size - 1);
         let elem = self.heap.remove(self.heap_size - 1);
+        self.map_remove(elem.clone(), self.heap_size - 1);
         self.heap_size -= 1;
 
         self.swim_down(0);
 
-        elem
+        Ok(elem)
     }
 
+    // Removing an element that isn't in the queue is a no-op, use try_remove to detect that
     pub fn remove(&mut self, elem: T) {
-        let index = self.get_index(elem.clone());
-        
-        if let Some(node_index) = index {
-            self.swap(node_index, self.heap_size - 1);
-            
-            self.heap.remove(self.heap_size - 1);
-            self.map_remove(elem, self.heap_size - 1);
-            
-            self.heap_size -= 1;
-            self.swim_up(node_index);
+        let _ = self.try_remove(elem);
+    }
+
+    pub fn try_remove(&mut self, elem: T) -> Result<(), DsaError> {
+        let node_index = self.get_index(elem.clone()).ok_or(DsaError::UnknownElement)?;
+
+        self.swap(node_index, self.heap_size - 1);
+
+        self.heap.remove(self.heap_size - 1);
+        self.map_remove(elem, self.heap_size - 1);
+
+        self.heap_size -= 1;
+
+        // The last node itself was removed, nothing moved into its place
+        if node_index == self.heap_size {
+            return Ok(());
         }
+
+        self.swim_up(node_index);
+        self.swim_down(node_index);
+
+        Ok(())
     }
 
     fn map_remove(&mut self, elem: T, index: usize) {
         if let Some(set) = self.map.get_mut(&elem) {
             set.remove(&index);
-            println!("{:?} {}", set, index);
             if set.is_empty() {
                 self.map.remove(&elem);
             }
@@ -132,7 +161,7 @@ impl <T: Ord + Debug + Hash + Clone> PriorityQueue<T> {
     }
 
     pub fn get_index(&self, elem: T) -> Option<usize> {
-        self.map.get(&elem).map_or(None, |set| set.first().cloned())
+        self.map.get(&elem).and_then(|set| set.first().cloned())
 
         // for i in 0..self.heap_size {
         //     if self.heap[i] == elem {
@@ -144,14 +173,38 @@ impl <T: Ord + Debug + Hash + Clone> PriorityQueue<T> {
     }
 
     pub fn contains(&self, elem: T) -> bool {
-        let index = self.get_index(elem);
-        match index {
-            Some(_) => true,
-            None => false
-        }
+        self.get_index(elem).is_some()
+    }
+}
+
+impl <T: Ord + Hash + Clone> Container for PriorityQueue<T> {
+    fn len(&self) -> usize {
+        self.heap_size
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+        self.map.clear();
+        self.heap_size = 0;
     }
 }
 
+impl <T: Ord + Hash + Clone> Buffer<T> for PriorityQueue<T> {
+    fn push(&mut self, val: T) {
+        self.add(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.poll()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        PriorityQueue::peek(self)
+    }
+}
+
+impl <T: Ord + Hash + Clone> PriorityBuffer<T> for PriorityQueue<T> {}
+
 // Min Priority Queue
 #[test]
 fn add_descending() {
@@ -165,10 +218,10 @@ fn add_descending() {
     // Should be
     // 1, 2, 5, 11
 
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 11);
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(11));
 }
 
 #[test]
@@ -183,10 +236,10 @@ fn add_ascending() {
     // Should be
     // 1, 2, 5, 11
 
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 11);
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(11));
 }
 
 #[test]
@@ -206,15 +259,15 @@ fn add_random() {
     // Should be
     // -5, 0, 1, 1, 2, 3, 5, 11, 99
 
-    assert_eq!(queue.poll(), -5);
-    assert_eq!(queue.poll(), 0);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 3);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 11);
-    assert_eq!(queue.poll(), 99);
+    assert_eq!(queue.poll(), Some(-5));
+    assert_eq!(queue.poll(), Some(0));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(3));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(11));
+    assert_eq!(queue.poll(), Some(99));
 }
 
 // Max Priority Queue
@@ -230,10 +283,10 @@ fn max_add_descending() {
     // Should be
     // 11, 5, 2, 1
 
-    assert_eq!(queue.poll(), 11);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 1);
+    assert_eq!(queue.poll(), Some(11));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(1));
 }
 
 #[test]
@@ -248,10 +301,10 @@ fn max_add_ascending() {
     // Should be
     // 11, 5, 2, 1
 
-    assert_eq!(queue.poll(), 11);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 1);
+    assert_eq!(queue.poll(), Some(11));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(1));
 }
 
 #[test]
@@ -271,15 +324,15 @@ fn max_add_random() {
     // Should be
     // 99, 11, 5, 3, 2, 1, 1, 0, -5
 
-    assert_eq!(queue.poll(), 99);
-    assert_eq!(queue.poll(), 11);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 3);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 0);
-    assert_eq!(queue.poll(), -5);
+    assert_eq!(queue.poll(), Some(99));
+    assert_eq!(queue.poll(), Some(11));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(3));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(0));
+    assert_eq!(queue.poll(), Some(-5));
 }
 
 // Removal
@@ -302,18 +355,19 @@ fn remove() {
     // Should be
     // -5, 1, 1, 2, 3, 5, 7, 11
 
-    assert_eq!(queue.poll(), -5);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 1);
-    assert_eq!(queue.poll(), 2);
-    assert_eq!(queue.poll(), 3);
-    assert_eq!(queue.poll(), 5);
-    assert_eq!(queue.poll(), 7);
-    assert_eq!(queue.poll(), 11);
+    assert_eq!(queue.poll(), Some(-5));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(1));
+    assert_eq!(queue.poll(), Some(2));
+    assert_eq!(queue.poll(), Some(3));
+    assert_eq!(queue.poll(), Some(5));
+    assert_eq!(queue.poll(), Some(7));
+    assert_eq!(queue.poll(), Some(11));
 }
 
 // Contains
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn contains() {
     let mut queue = PriorityQueue::<isize>::new(Sort::Min);
 
@@ -329,10 +383,50 @@ fn contains() {
     assert_eq!(queue.contains(1), false);
     queue.remove(-5);
 
-    println!("{:?}", queue.heap);
-    println!("{:?}", queue.map);
-
     assert_eq!(queue.contains(-5), false);
     queue.remove(11);
     assert_eq!(queue.contains(11), false);
 }
+
+#[test]
+fn try_poll_remove() {
+    let mut queue = PriorityQueue::<isize>::new(Sort::Min);
+
+    assert_eq!(queue.try_poll(), Err(DsaError::Empty));
+    assert_eq!(queue.try_peek(), Err(DsaError::Empty));
+    assert_eq!(queue.poll(), None);
+
+    queue.add(3);
+    queue.add(1);
+
+    assert_eq!(queue.try_remove(7), Err(DsaError::UnknownElement));
+    assert_eq!(queue.try_peek(), Ok(&1));
+    assert_eq!(queue.try_remove(1), Ok(()));
+    assert_eq!(queue.try_poll(), Ok(3));
+    assert!(!queue.contains(3));
+    assert_eq!(queue.try_poll(), Err(DsaError::Empty));
+}
+
+#[test]
+fn max_try_poll_remove() {
+    let mut queue = PriorityQueue::<isize>::new(Sort::Max);
+
+    for val in [4, 9, 1, 7, 9, 3, 8, 2] {
+        queue.add(val);
+    }
+
+    assert_eq!(queue.try_remove(9), Ok(()));
+    assert_eq!(queue.try_remove(2), Ok(()));
+    assert_eq!(queue.try_remove(5), Err(DsaError::UnknownElement));
+    assert_eq!(queue.try_peek(), Ok(&9));
+
+    // Removing the last node in the heap leaves the rest in place
+    queue.add(0);
+    assert_eq!(queue.try_remove(0), Ok(()));
+
+    for expected in [9, 8, 7, 4, 3, 1] {
+        assert_eq!(queue.try_poll(), Ok(expected));
+    }
+
+    assert_eq!(queue.try_poll(), Err(DsaError::Empty));
+}
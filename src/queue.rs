@@ -1,17 +1,26 @@
 #![allow(dead_code, unused_variables)]
+
+use crate::{error::DsaError, traits::{Buffer, Container, FifoBuffer}};
+
 // Dynamic queue
-struct Queue<T>(Vec<T>);
+pub struct Queue<T>(Vec<T>);
+
+impl <T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl <T> Queue<T> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Queue(Vec::new())
     }
 
-    fn enqueue(&mut self, val: T) {
+    pub fn enqueue(&mut self, val: T) {
         self.0.push(val);
     }
 
-    fn peek(&self) -> Option<&T> {
+    pub fn peek(&self) -> Option<&T> {
         if !self.is_empty() {
             Some(&self.0[0])
         } else {
@@ -19,7 +28,7 @@ impl <T> Queue<T> {
         }
     }
 
-    fn dequeue(&mut self) -> Option<T> {
+    pub fn dequeue(&mut self) -> Option<T> {
         if !self.is_empty() {
             Some(self.0.remove(0))
         } else {
@@ -27,15 +36,49 @@ impl <T> Queue<T> {
         }
     }
     
-    fn size(&self) -> usize {
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
+    pub fn try_dequeue(&mut self) -> Result<T, DsaError> {
+        self.dequeue().ok_or(DsaError::Empty)
+    }
+
+    pub fn size(&self) -> usize {
         self.0.len()
     }
 
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 }
 
+impl <T> Container for Queue<T> {
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl <T> Buffer<T> for Queue<T> {
+    fn push(&mut self, val: T) {
+        self.enqueue(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Queue::peek(self)
+    }
+}
+
+impl <T> FifoBuffer<T> for Queue<T> {}
+
 #[test]
 fn enqueue_dequeue() {
     let mut queue = Queue::new();
@@ -54,37 +97,49 @@ fn enqueue_dequeue() {
 }
 
 #[derive(Debug)]
-struct StaticQueue<T, const N: usize> {
+pub struct StaticQueue<T, const N: usize> {
     list: [T; N],
     size: usize
 }
 
+impl <T: Default + Copy, const N: usize> Default for StaticQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl <T: Default + Copy, const N: usize> StaticQueue<T, N> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         StaticQueue {
             list: [T::default(); N],
             size: 0
         }
     }
 
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.size == 0
     }
 
-    fn enqueue(&mut self, val: T) {
+    // Values enqueued on a full queue are discarded, use try_enqueue to detect that
+    pub fn enqueue(&mut self, val: T) {
+        let _ = self.try_enqueue(val);
+    }
+
+    pub fn try_enqueue(&mut self, val: T) -> Result<(), DsaError> {
         if self.size < N {
             self.list[self.size] = val;
             self.size += 1;
+            Ok(())
+        } else {
+            Err(DsaError::Full)
         }
     }
 
-    fn dequeue(&mut self) -> Option<T> {
+    pub fn dequeue(&mut self) -> Option<T> {
         if !self.is_empty() {
             let mut new_list = [T::default(); N];
 
-            for index in 0..N-1 {
-                new_list[index] = self.list[index + 1];
-            }
+            new_list[..N-1].copy_from_slice(&self.list[1..]);
 
             let to_return = self.list[0];
             self.list = new_list;
@@ -95,7 +150,7 @@ impl <T: Default + Copy, const N: usize> StaticQueue<T, N> {
         }
     }
 
-    fn peek(&self) -> Option<&T> {
+    pub fn peek(&self) -> Option<&T> {
         if !self.is_empty() {
             Some(&self.list[0])
         } else {
@@ -103,11 +158,49 @@ impl <T: Default + Copy, const N: usize> StaticQueue<T, N> {
         }
     }
 
-    fn size(&self) -> usize {
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
+    pub fn try_dequeue(&mut self) -> Result<T, DsaError> {
+        self.dequeue().ok_or(DsaError::Empty)
+    }
+
+    pub fn size(&self) -> usize {
         self.size
     }
 }
 
+impl <T: Default + Copy, const N: usize> Container for StaticQueue<T, N> {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn clear(&mut self) {
+        self.size = 0;
+    }
+}
+
+impl <T: Default + Copy, const N: usize> Buffer<T> for StaticQueue<T, N> {
+    fn push(&mut self, val: T) {
+        self.enqueue(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        StaticQueue::peek(self)
+    }
+
+    fn try_push(&mut self, val: T) -> Result<(), DsaError> {
+        self.try_enqueue(val)
+    }
+}
+
+impl <T: Default + Copy, const N: usize> FifoBuffer<T> for StaticQueue<T, N> {}
+
 #[test]
 fn static_enqueue_dequeue() {
     let mut queue = StaticQueue::<i32, 4>::new();
@@ -125,3 +218,20 @@ fn static_enqueue_dequeue() {
     assert_eq!(queue.dequeue(), Some(9));
     assert_eq!(queue.dequeue(), None);
 }
+
+
+#[test]
+fn try_enqueue_dequeue() {
+    let mut queue = Queue::<i32>::new();
+    assert_eq!(queue.try_peek(), Err(DsaError::Empty));
+    assert_eq!(queue.try_dequeue(), Err(DsaError::Empty));
+
+    let mut queue = StaticQueue::<i32, 2>::new();
+    assert_eq!(queue.try_enqueue(1), Ok(()));
+    assert_eq!(queue.try_enqueue(2), Ok(()));
+    assert_eq!(queue.try_enqueue(3), Err(DsaError::Full));
+    assert_eq!(queue.try_dequeue(), Ok(1));
+    assert_eq!(queue.try_peek(), Ok(&2));
+    assert_eq!(queue.try_dequeue(), Ok(2));
+    assert_eq!(queue.try_dequeue(), Err(DsaError::Empty));
+}
@@ -0,0 +1,421 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hasher},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull
+};
+
+use crate::traits::Container;
+
+const MAX_LEVEL: usize = 32;
+
+type NodePointer<K, V> = NonNull<SkipNode<K, V>>;
+
+// ------------------------------ Node ------------------------------
+// forward[level] is the next node on that level, every node is on levels 0..forward.len()
+struct SkipNode<K, V> {
+    key: K,
+    val: V,
+    forward: Vec<Option<NodePointer<K, V>>>
+}
+
+// ------------------------------ Random Levels ------------------------------
+// splitmix64, small and good enough to pick node heights without pulling in a crate
+struct LevelGenerator(u64);
+
+impl LevelGenerator {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Every extra level is kept with probability 1/2
+    fn level(&mut self) -> usize {
+        (self.next_u64().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+// ------------------------------ Skip List ------------------------------
+// Ordered map made of sorted linked lists stacked on top of each other, each level
+// skipping over roughly half of the nodes of the level below it. Searches start at the
+// highest level and drop down a level whenever the next key would overshoot
+pub struct SkipList<K, V> {
+    head: [Option<NodePointer<K, V>>; MAX_LEVEL],
+    level: usize,
+    length: usize,
+    levels: LevelGenerator,
+    phantom: PhantomData<Box<SkipNode<K, V>>>
+}
+
+impl <K: Ord, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <K: Ord, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    // The same seed and the same operations always build the same node levels
+    pub fn with_seed(seed: u64) -> Self {
+        SkipList {
+            head: [None; MAX_LEVEL],
+            level: 0,
+            length: 0,
+            levels: LevelGenerator(seed),
+            phantom: PhantomData
+        }
+    }
+
+    // Inserts or replaces a value, returning the old one
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let update = self.predecessors(&key);
+
+        if let Some(node) = self.next_of(update[0], 0) {
+            if unsafe { (*node.as_ptr()).key == key } {
+                return Some(std::mem::replace(unsafe { &mut (*node.as_ptr()).val }, val));
+            }
+        }
+
+        // Levels above the current height start straight from the head, which update already holds
+        let node_level = self.levels.level();
+        self.level = self.level.max(node_level);
+
+        let node = NonNull::from(Box::leak(Box::new(SkipNode { key, val, forward: vec![None; node_level] })));
+
+        for (level, &predecessor) in update.iter().enumerate().take(node_level) {
+            unsafe { (&mut (*node.as_ptr()).forward)[level] = self.next_of(predecessor, level) };
+            self.set_next(predecessor, level, Some(node));
+        }
+
+        self.length += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(key);
+        let node = self.next_of(update[0], 0).filter(|node| unsafe { (*node.as_ptr()).key == *key })?;
+
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+
+        for (level, &next) in node.forward.iter().enumerate() {
+            self.set_next(update[level], level, next);
+        }
+
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+
+        self.length -= 1;
+        Some(node.val)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find(key).map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.head[0].map(|node| unsafe { entry(node) })
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut current = None;
+
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next_of(current, level) {
+                current = Some(next);
+            }
+        }
+
+        current.map(|node| unsafe { entry(node) })
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { current: self.head[0], stop: None, phantom: PhantomData }
+    }
+
+    // Entries with keys inside the range in ascending order, e.g. list.range(2..5)
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Iter<'_, K, V> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.first_where(|key| key >= start),
+            Bound::Excluded(start) => self.first_where(|key| key > start),
+            Bound::Unbounded => self.head[0]
+        };
+
+        let stop = match range.end_bound() {
+            Bound::Included(end) => self.first_where(|key| key > end),
+            Bound::Excluded(end) => self.first_where(|key| key >= end),
+            Bound::Unbounded => None
+        };
+
+        // A start past the end, as in 5..2, would otherwise walk straight past stop
+        let current = start.filter(|&node| range.contains(unsafe { &(*node.as_ptr()).key }));
+
+        Iter { current, stop, phantom: PhantomData }
+    }
+
+    fn find(&self, key: &K) -> Option<NodePointer<K, V>> {
+        self.first_where(|node_key| node_key >= key)
+            .filter(|node| unsafe { (*node.as_ptr()).key == *key })
+    }
+
+    // First node whose key satisfies a predicate that stays true once it becomes true
+    fn first_where<F: Fn(&K) -> bool>(&self, predicate: F) -> Option<NodePointer<K, V>> {
+        let mut current = None;
+
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next_of(current, level) {
+                if predicate(unsafe { &(*next.as_ptr()).key }) {
+                    break;
+                }
+
+                current = Some(next);
+            }
+        }
+
+        self.next_of(current, 0)
+    }
+
+    // Last node before key on every level, None standing for the head
+    fn predecessors(&self, key: &K) -> [Option<NodePointer<K, V>>; MAX_LEVEL] {
+        let mut update = [None; MAX_LEVEL];
+        let mut current = None;
+
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next_of(current, level) {
+                if unsafe { (*next.as_ptr()).key >= *key } {
+                    break;
+                }
+
+                current = Some(next);
+            }
+
+            update[level] = current;
+        }
+
+        update
+    }
+}
+
+impl <K, V> SkipList<K, V> {
+    fn next_of(&self, node: Option<NodePointer<K, V>>, level: usize) -> Option<NodePointer<K, V>> {
+        match node {
+            Some(node) => unsafe { (&(*node.as_ptr()).forward)[level] },
+            None => self.head[level]
+        }
+    }
+
+    fn set_next(&mut self, node: Option<NodePointer<K, V>>, level: usize, next: Option<NodePointer<K, V>>) {
+        match node {
+            Some(node) => unsafe { (&mut (*node.as_ptr()).forward)[level] = next },
+            None => self.head[level] = next
+        }
+    }
+
+    fn free_nodes(&mut self) {
+        let mut current = self.head[0];
+
+        while let Some(node) = current {
+            current = unsafe { Box::from_raw(node.as_ptr()).forward[0] };
+        }
+
+        self.head = [None; MAX_LEVEL];
+        self.level = 0;
+        self.length = 0;
+    }
+}
+
+impl <K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        self.free_nodes();
+    }
+}
+
+impl <K: Ord, V> Container for SkipList<K, V> {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        self.free_nodes();
+    }
+}
+
+impl <K: Debug, V: Debug> Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = Iter { current: self.head[0], stop: None, phantom: PhantomData };
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl <K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+
+        for (key, val) in iter {
+            list.insert(key, val);
+        }
+
+        list
+    }
+}
+
+unsafe fn entry<'a, K, V>(node: NodePointer<K, V>) -> (&'a K, &'a V) {
+    (&(*node.as_ptr()).key, &(*node.as_ptr()).val)
+}
+
+// ------------------------------ Iterator ------------------------------
+// Walks level 0 until it reaches stop, the first node past the range
+pub struct Iter<'a, K, V> {
+    current: Option<NodePointer<K, V>>,
+    stop: Option<NodePointer<K, V>>,
+    phantom: PhantomData<&'a SkipNode<K, V>>
+}
+
+impl <'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.filter(|&node| Some(node) != self.stop)?;
+        self.current = unsafe { (&(*node.as_ptr()).forward)[0] };
+        Some(unsafe { entry(node) })
+    }
+}
+
+impl <'a, K: Ord, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Inserted 0, 10, 20, ..., 90 out of order
+#[cfg(test)]
+fn init_test_list() -> SkipList<i32, String> {
+    let mut list = SkipList::with_seed(7);
+
+    for key in [50, 10, 90, 0, 30, 70, 20, 80, 40, 60] {
+        list.insert(key, key.to_string());
+    }
+
+    list
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut list = init_test_list();
+
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.get(&30), Some(&"30".to_string()));
+    assert_eq!(list.get(&35), None);
+    assert_eq!(list.insert(30, "thirty".to_string()), Some("30".to_string()));
+    assert_eq!(list.len(), 10);
+
+    if let Some(val) = list.get_mut(&40) {
+        val.push('!');
+    }
+
+    assert_eq!(list.get(&40), Some(&"40!".to_string()));
+    assert_eq!(list.remove(&30), Some("thirty".to_string()));
+    assert_eq!(list.remove(&30), None);
+    assert!(!list.contains_key(&30));
+    assert_eq!(list.len(), 9);
+
+    let keys: Vec<_> = list.iter().map(|(&key, _)| key).collect();
+    assert_eq!(keys, vec![0, 10, 20, 40, 50, 60, 70, 80, 90]);
+}
+
+#[test]
+fn first_last() {
+    let mut list = init_test_list();
+
+    assert_eq!(list.first().map(|(&key, _)| key), Some(0));
+    assert_eq!(list.last().map(|(&key, _)| key), Some(90));
+
+    list.remove(&0);
+    list.remove(&90);
+    assert_eq!(list.first().map(|(&key, _)| key), Some(10));
+    assert_eq!(list.last().map(|(&key, _)| key), Some(80));
+
+    list.clear();
+    assert_eq!(list.first(), None);
+    assert_eq!(list.last(), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn range() {
+    let list = init_test_list();
+    let keys = |iter: Iter<'_, i32, String>| iter.map(|(&key, _)| key).collect::<Vec<_>>();
+
+    assert_eq!(keys(list.range(20..50)), vec![20, 30, 40]);
+    assert_eq!(keys(list.range(15..=50)), vec![20, 30, 40, 50]);
+    assert_eq!(keys(list.range(..20)), vec![0, 10]);
+    assert_eq!(keys(list.range(75..)), vec![80, 90]);
+    assert_eq!(keys(list.range((Bound::Excluded(80), Bound::Unbounded))), vec![90]);
+    assert_eq!(keys(list.range(31..39)), vec![]);
+    assert_eq!(keys(list.range((Bound::Included(50), Bound::Excluded(20)))), vec![]);
+    assert_eq!(keys(list.range(..)).len(), 10);
+}
+
+#[test]
+fn seeded_lists_are_reproducible() {
+    let levels = |list: &SkipList<i32, String>| {
+        let mut levels = Vec::new();
+        let mut current = list.head[0];
+
+        while let Some(node) = current {
+            unsafe {
+                levels.push((*node.as_ptr()).forward.len());
+                current = (&(*node.as_ptr()).forward)[0];
+            }
+        }
+
+        levels
+    };
+
+    assert_eq!(levels(&init_test_list()), levels(&init_test_list()));
+    assert_eq!(format!("{:?}", SkipList::<i32, i32>::with_seed(1)), "{}");
+}
+
+#[test]
+fn matches_btree_map() {
+    use std::collections::BTreeMap;
+
+    let mut list = SkipList::with_seed(42);
+    let mut map = BTreeMap::new();
+    let mut seed = 99u32;
+
+    for step in 0..5000 {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        let key = (seed >> 8) % 500;
+
+        if step % 3 == 0 {
+            assert_eq!(list.remove(&key), map.remove(&key));
+        } else {
+            assert_eq!(list.insert(key, step), map.insert(key, step));
+        }
+    }
+
+    assert_eq!(list.len(), map.len());
+    assert!(list.iter().eq(map.iter()));
+    assert!(list.range(100..200).eq(map.range(100..200)));
+    assert_eq!(list.last(), map.iter().next_back());
+}
@@ -1,10 +1,11 @@
 #![allow(dead_code, unused_variables)]
 
-use std::{borrow::Borrow, collections::HashMap};
+use std::{fmt::{self, Debug}, iter::Rev, mem::MaybeUninit, ptr, slice};
+
+use crate::{delimiter_checker::DelimiterChecker, error::DsaError, traits::{Buffer, Container, LifoBuffer}};
 type NodePointer<T> = Box<Node<T>>;
 
 // ------------------------------ Node ------------------------------
-#[derive(Debug)]
 struct Node<T> {
     val: T,
     next: Option<NodePointer<T>>
@@ -17,10 +18,15 @@ impl <T> Node<T> {
 }
 
 // ------------------------------ Stack ------------------------------
-#[derive(Debug)]
 pub struct Stack<T> {
     head: Option<NodePointer<T>>,
-    pub size: u32
+    size: usize
+}
+
+impl <T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl <T> Stack<T> {
@@ -48,35 +54,127 @@ impl <T> Stack<T> {
         })
     }
 
+    pub fn try_pop(&mut self) -> Result<T, DsaError> {
+        self.pop().ok_or(DsaError::Empty)
+    }
+
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.head.is_none()
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    // Unlinks one node at a time, letting the boxes drop on their own would recurse through
+    // the whole chain and overflow the thread stack for long stacks
+    pub fn clear(&mut self) {
+        let mut current_node = self.head.take();
+
+        while let Some(mut node) = current_node {
+            current_node = node.next.take();
+        }
+
+        self.size = 0;
+    }
+
+    // Keeps the len values closest to the top and drops the rest from the bottom in a single
+    // walk, the way to drop from the bottom since nodes only link downwards
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.size {
+            return;
+        }
+
+        if len == 0 {
+            self.clear();
+            return;
+        }
+
+        let mut node = self.head.as_mut().expect("stack holds more than len values");
+
+        for _ in 1..len {
+            node = node.next.as_mut().expect("stack holds more than len values");
+        }
+
+        // The cut off nodes go into a stack of their own so they are freed iteratively
+        let rest = Stack { head: node.next.take(), size: self.size - len };
+        self.size = len;
+        drop(rest);
     }
 
-    pub fn iter(&self) -> Iter<T> {
-        Iter { current_node: self.head.as_ref() }
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { current_node: self.head.as_ref(), remaining: self.size }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut { current_node: self.head.as_mut() }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { current_node: self.head.as_mut(), remaining: self.size }
     }
 }
 
-// ------------------------------ Stack ------------------------------
+impl <T> Container for Stack<T> {
+    fn len(&self) -> usize {
+        Stack::len(self)
+    }
+
+    fn clear(&mut self) {
+        Stack::clear(self)
+    }
+}
+
+impl <T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl <T> Buffer<T> for Stack<T> {
+    fn push(&mut self, val: T) {
+        Stack::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Stack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Stack::peek(self)
+    }
+}
+
+impl <T> LifoBuffer<T> for Stack<T> {}
+
+// ------------------------------ Iterator ------------------------------
 pub struct IntoIter<T> (Stack<T>);
 
+impl <T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
 impl <T> Iterator for IntoIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size, Some(self.0.size))
+    }
 }
 
+impl <T> ExactSizeIterator for IntoIter<T> {}
+
 pub struct Iter<'a, T> {
-    current_node: Option<&'a NodePointer<T>>
+    current_node: Option<&'a NodePointer<T>>,
+    remaining: usize
 }
 
 impl <'a, T> Iterator for Iter<'a, T> {
@@ -85,13 +183,27 @@ impl <'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.current_node.take().map(|node| {
             self.current_node = node.next.as_ref();
+            self.remaining -= 1;
             &node.val
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> ExactSizeIterator for Iter<'_, T> {}
+
+impl <T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { current_node: self.current_node, remaining: self.remaining }
+    }
 }
 
 pub struct IterMut<'a, T> {
-    current_node: Option<&'a mut NodePointer<T>>
+    current_node: Option<&'a mut NodePointer<T>>,
+    remaining: usize
 }
 
 impl <'a, T> Iterator for IterMut<'a, T> {
@@ -100,9 +212,74 @@ impl <'a, T> Iterator for IterMut<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.current_node.take().map(|node| {
             self.current_node = node.next.as_mut();
+            self.remaining -= 1;
             &mut node.val
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl <'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl <'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// ------------------------------ Traits ------------------------------
+// Values are pushed in iteration order, so the last one ends up on top
+impl <T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl <T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl <T: Clone> Clone for Stack<T> {
+    fn clone(&self) -> Self {
+        let vals: Vec<&T> = self.iter().collect();
+        vals.into_iter().rev().cloned().collect()
+    }
+}
+
+impl <T: PartialEq> PartialEq for Stack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: Eq> Eq for Stack<T> {}
+
+// Lists the values from the top down
+impl <T: Debug> Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
 }
 
 // pushed 1-6 on the stack in that exact order
@@ -145,6 +322,7 @@ fn peek() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn is_empty() {
     let mut stack = Stack::<i32>::new();
 
@@ -155,6 +333,17 @@ fn is_empty() {
     assert_eq!(stack.is_empty(), true);
 }
 
+#[test]
+fn try_pop_peek() {
+    let mut stack = Stack::<i32>::new();
+
+    assert_eq!(stack.try_peek(), Err(DsaError::Empty));
+    assert_eq!(stack.try_pop(), Err(DsaError::Empty));
+    stack.push(7);
+    assert_eq!(stack.try_peek(), Ok(&7));
+    assert_eq!(stack.try_pop(), Ok(7));
+}
+
 #[test]
 fn into_iter() {
     let mut iter = init_stack().into_iter();
@@ -196,43 +385,342 @@ fn iter_mut() {
     assert_eq!(iter.next(), None);
 }
 
-// ------------------------------ Application ------------------------------
-fn brackets_are_valid(brackets: &str) -> bool {
+#[test]
+fn exact_size() {
+    let mut stack = init_stack();
+
+    let mut iter = stack.iter();
+    iter.next();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(stack.iter_mut().skip(2).len(), 4);
+
+    stack.pop();
+    let mut iter = stack.into_iter();
+    assert_eq!(iter.len(), 5);
+    iter.next();
+    assert_eq!(iter.len(), 4);
+}
+
+#[test]
+fn collect_extend_clone() {
+    let mut stack: Stack<i32> = (1..=3).collect();
+
+    assert_eq!(stack.peek(), Some(&3));
+    stack.extend([4, 5]);
+    assert_eq!(stack.len(), 5);
+
+    for val in &mut stack {
+        *val *= 2;
+    }
+
+    let copy = stack.clone();
+    assert_eq!(copy, stack);
+    assert_eq!((&copy).into_iter().copied().collect::<Vec<_>>(), vec![10, 8, 6, 4, 2]);
+
+    stack.pop();
+    assert_ne!(copy, stack);
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", init_stack()), "[6, 5, 4, 3, 2, 1]");
+    assert_eq!(format!("{:?}", Stack::<i32>::new()), "[]");
+}
+
+#[test]
+fn truncate() {
+    let mut stack = init_stack();
+
+    stack.truncate(2);
+    assert_eq!(stack, (5..=6).collect());
+    stack.truncate(5);
+    assert_eq!(stack.len(), 2);
+    stack.truncate(0);
+    assert!(stack.is_empty());
+
+    stack.push(7);
+    stack.truncate(1);
+    assert_eq!(stack.pop(), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn drop_long_stack() {
     let mut stack = Stack::new();
 
-    let closing_brackets = [']', ')', '}'];
-    let mut bracket_pairs = HashMap::new();
-    bracket_pairs.insert('(', ')');
-    bracket_pairs.insert('[', ']');
-    bracket_pairs.insert('{', '}');
-
-    for bracket in brackets.chars() {
-        // Checking if bracket is a closing bracket
-        if closing_brackets.contains(&bracket) {
-            // Checking if the bracket is the closing pair for the bracket that's the head
-            if let Some(head) = stack.peek() {
-                let head_closing_pair = bracket_pairs.get(head);
-                if head_closing_pair == Some(bracket.borrow()) {
-                    stack.pop();
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
-        } else {
-            stack.push(bracket);
+    for val in 0..5_000_000 {
+        stack.push(val);
+    }
+
+    stack.clear();
+    assert!(stack.is_empty());
+    assert_eq!(stack.len(), 0);
+
+    for val in 0..5_000_000 {
+        stack.push(val);
+    }
+
+    drop(stack);
+}
+
+// ------------------------------ Static Stack ------------------------------
+// Fixed capacity stack stored inline in an array, it never allocates. Only the first size
+// slots are initialised, so values need neither Default nor Copy
+pub struct StaticStack<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    size: usize
+}
+
+impl <T, const N: usize> Default for StaticStack<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T, const N: usize> StaticStack<T, N> {
+    pub const fn new() -> Self {
+        StaticStack { items: [const { MaybeUninit::uninit() }; N], size: 0 }
+    }
+
+    // Hands the value back when the stack is full
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        if self.size == N {
+            return Err(val);
+        }
+
+        self.items[self.size].write(val);
+        self.size += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        self.size -= 1;
+        Some(unsafe { self.items[self.size].assume_init_read() })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    pub fn try_pop(&mut self) -> Result<T, DsaError> {
+        self.pop().ok_or(DsaError::Empty)
+    }
+
+    pub fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size == N
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    // Values from the bottom to the top of the stack
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.size) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.size) }
+    }
+
+    // Values from the top to the bottom of the stack, like Stack::iter
+    pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+        self.as_slice().iter().rev()
+    }
+
+    pub fn iter_mut(&mut self) -> Rev<slice::IterMut<'_, T>> {
+        self.as_mut_slice().iter_mut().rev()
+    }
+}
+
+impl <T, const N: usize> Drop for StaticStack<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl <T: Clone, const N: usize> Clone for StaticStack<T, N> {
+    fn clone(&self) -> Self {
+        let mut stack = StaticStack::new();
+
+        for val in self.as_slice() {
+            let _ = stack.push(val.clone());
         }
+
+        stack
     }
+}
+
+impl <T: Debug, const N: usize> Debug for StaticStack<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl <T, const N: usize> Container for StaticStack<T, N> {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn clear(&mut self) {
+        let values: *mut [T] = self.as_mut_slice();
+        self.size = 0;
+        unsafe { ptr::drop_in_place(values) };
+    }
+}
 
-    stack.is_empty()
+impl <T, const N: usize> Buffer<T> for StaticStack<T, N> {
+    // Values pushed on a full stack are discarded, use try_push to detect that
+    fn push(&mut self, val: T) {
+        let _ = StaticStack::push(self, val);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        StaticStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        StaticStack::peek(self)
+    }
+
+    fn try_push(&mut self, val: T) -> Result<(), DsaError> {
+        StaticStack::push(self, val).map_err(|_| DsaError::Full)
+    }
+}
+
+impl <T, const N: usize> LifoBuffer<T> for StaticStack<T, N> {}
+
+pub struct StaticIntoIter<T, const N: usize> (StaticStack<T, N>);
+
+impl <T, const N: usize> IntoIterator for StaticStack<T, N> {
+    type Item = T;
+    type IntoIter = StaticIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        StaticIntoIter(self)
+    }
+}
+
+impl <T, const N: usize> Iterator for StaticIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size, Some(self.0.size))
+    }
+}
+
+impl <T, const N: usize> ExactSizeIterator for StaticIntoIter<T, N> {}
+
+impl <'a, T, const N: usize> IntoIterator for &'a StaticStack<T, N> {
+    type Item = &'a T;
+    type IntoIter = Rev<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl <'a, T, const N: usize> IntoIterator for &'a mut StaticStack<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = Rev<slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[test]
+fn static_push_pop() {
+    let mut stack = StaticStack::<String, 2>::new();
+
+    assert_eq!(stack.peek(), None);
+    assert_eq!(stack.push("a".to_string()), Ok(()));
+    assert_eq!(stack.push("b".to_string()), Ok(()));
+    assert!(stack.is_full());
+    assert_eq!(stack.push("c".to_string()), Err("c".to_string()));
+    assert_eq!(stack.as_slice(), ["a", "b"]);
+    assert_eq!(stack.peek().map(String::as_str), Some("b"));
+    assert_eq!(stack.pop().as_deref(), Some("b"));
+    assert_eq!(stack.try_pop().as_deref(), Ok("a"));
+    assert_eq!(stack.try_pop(), Err(DsaError::Empty));
+    assert_eq!(Buffer::try_push(&mut StaticStack::<i32, 0>::new(), 1), Err(DsaError::Full));
+}
+
+#[test]
+fn static_iterators() {
+    let mut stack = StaticStack::<i32, 8>::new();
+
+    for val in 1..=4 {
+        let _ = stack.push(val);
+    }
+
+    for val in &mut stack {
+        *val *= 10;
+    }
+
+    assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![40, 30, 20, 10]);
+    assert_eq!(format!("{:?}", stack.clone()), "[40, 30, 20, 10]");
+
+    let mut iter = stack.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(40));
+    assert_eq!(iter.collect::<Vec<_>>(), vec![30, 20, 10]);
+}
+
+#[test]
+fn static_drops_values() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut stack = StaticStack::<Rc<()>, 4>::new();
+
+    for _ in 0..4 {
+        let _ = stack.push(counter.clone());
+    }
+
+    stack.pop();
+    assert_eq!(Rc::strong_count(&counter), 4);
+
+    let mut iter = stack.into_iter();
+    iter.next();
+    drop(iter);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+// ------------------------------ Application ------------------------------
+// Round, square and curly brackets, see DelimiterChecker for positions and other tokens
+fn brackets_are_valid(brackets: &str) -> bool {
+    DelimiterChecker::new()
+        .pair("(", ")")
+        .pair("[", "]")
+        .pair("{", "}")
+        .is_valid(brackets)
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn check_brackets_are_valid() {
     assert_eq!(brackets_are_valid("[({})]"), true);
     assert_eq!(brackets_are_valid("[(})]"), false);
     assert_eq!(brackets_are_valid("({])"), false);
     assert_eq!(brackets_are_valid("{}"), true);
     assert_eq!(brackets_are_valid(""), true);
+    assert_eq!(brackets_are_valid("a"), true);
+    assert_eq!(brackets_are_valid("]"), false);
 }
\ No newline at end of file
@@ -0,0 +1,143 @@
+use crate::error::DsaError;
+
+// Common size queries shared by every container
+pub trait Container {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self);
+}
+
+// Anything values can be pushed to and popped from, the order is decided by the sub traits
+pub trait Buffer<T>: Container {
+    fn push(&mut self, val: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn peek(&self) -> Option<&T>;
+
+    // Bounded buffers override this to report DsaError::Full
+    fn try_push(&mut self, val: T) -> Result<(), DsaError> {
+        self.push(val);
+        Ok(())
+    }
+
+    fn try_pop(&mut self) -> Result<T, DsaError> {
+        self.pop().ok_or(DsaError::Empty)
+    }
+
+    fn try_peek(&self) -> Result<&T, DsaError> {
+        self.peek().ok_or(DsaError::Empty)
+    }
+}
+
+// pop returns the most recently pushed value
+pub trait LifoBuffer<T>: Buffer<T> {}
+
+// pop returns the least recently pushed value
+pub trait FifoBuffer<T>: Buffer<T> {}
+
+// pop returns the value with the highest priority
+pub trait PriorityBuffer<T>: Buffer<T> {}
+
+// Pops every value out of the buffer in the order the buffer hands them out
+pub fn drain<T, B: Buffer<T>>(buffer: &mut B) -> Vec<T> {
+    let mut values = Vec::with_capacity(buffer.len());
+
+    while let Some(val) = buffer.pop() {
+        values.push(val);
+    }
+
+    values
+}
+
+#[cfg(test)]
+use crate::{LinkedList, PriorityQueue, Queue, Sort, Stack, StaticQueue};
+
+#[cfg(test)]
+fn fill<B: Buffer<i32>>(mut buffer: B) -> B {
+    for val in [3, 1, 2] {
+        buffer.push(val);
+    }
+
+    buffer
+}
+
+#[test]
+fn lifo() {
+    let mut stack = fill(Stack::new());
+
+    assert_eq!(stack.len(), 3);
+    assert_eq!(drain(&mut stack), vec![2, 1, 3]);
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn fifo() {
+    let mut queue = fill(Queue::new());
+    let mut static_queue = fill(StaticQueue::<i32, 3>::new());
+    let mut list = fill(LinkedList::new());
+
+    assert_eq!(static_queue.try_push(4), Err(DsaError::Full));
+    assert_eq!(drain(&mut queue), vec![3, 1, 2]);
+    assert_eq!(drain(&mut static_queue), vec![3, 1, 2]);
+    assert_eq!(drain(&mut list), vec![3, 1, 2]);
+}
+
+#[test]
+fn priority() {
+    let mut min_queue = fill(PriorityQueue::new(Sort::Min));
+    let mut max_queue = fill(PriorityQueue::new(Sort::Max));
+
+    assert_eq!(min_queue.peek(), Some(&1));
+    assert_eq!(drain(&mut min_queue), vec![1, 2, 3]);
+    assert_eq!(drain(&mut max_queue), vec![3, 2, 1]);
+}
+
+// Pushes pseudo random values, duplicates included, and pops them back through the trait
+#[cfg(test)]
+fn drain_random<B: PriorityBuffer<i32>>(buffer: &mut B, seed: u64, count: usize) -> (Vec<i32>, Vec<i32>) {
+    let mut state = seed;
+    let mut pushed = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let val = (state % 64) as i32 - 32;
+        buffer.push(val);
+        pushed.push(val);
+    }
+
+    (pushed, drain(buffer))
+}
+
+#[test]
+fn priority_random() {
+    for (seed, count) in (1..=20).zip([0, 1, 2, 3, 7, 8, 9, 15, 16, 17, 31, 64, 100, 127, 128, 129, 255, 500, 777, 1000]) {
+        let (mut pushed, popped) = drain_random(&mut PriorityQueue::new(Sort::Min), seed, count);
+        pushed.sort();
+        assert_eq!(popped, pushed);
+
+        let (mut pushed, popped) = drain_random(&mut PriorityQueue::new(Sort::Max), seed, count);
+        pushed.sort_by(|a, b| b.cmp(a));
+        assert_eq!(popped, pushed);
+    }
+}
+
+#[test]
+fn clear() {
+    let mut stack = fill(Stack::new());
+    let mut queue = fill(PriorityQueue::new(Sort::Min));
+
+    stack.clear();
+    queue.clear();
+
+    assert_eq!(stack.len(), 0);
+    assert_eq!(queue.len(), 0);
+    assert_eq!(queue.pop(), None);
+}
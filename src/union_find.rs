@@ -1,8 +1,11 @@
 #![allow(dead_code)]
 
 use std::{collections::HashMap, hash::Hash};
+
+use crate::error::DsaError;
+
 #[derive(Debug)]
-struct UnionFind<T> {
+pub struct UnionFind<T> {
     map: HashMap<T, usize>,
     pub array: Vec<usize>
 }
@@ -26,10 +29,23 @@ impl <T: Eq + Hash> UnionFind<T> {
             root = self.array[root]
         }
 
-        return  root;
+        root
+    }
+
+    pub fn try_find(&self, node: usize) -> Result<usize, DsaError> {
+        if node < self.array.len() {
+            Ok(self.find(node))
+        } else {
+            Err(DsaError::IndexOutOfBounds { index: node, len: self.array.len() })
+        }
     }
 
+    // Unknown nodes are ignored, use try_union to detect that
     pub fn union(&mut self, node1: T, node2: T) {
+        let _ = self.try_union(node1, node2);
+    }
+
+    pub fn try_union(&mut self, node1: T, node2: T) -> Result<(), DsaError> {
         if let (Some(&node1_label), Some(&node2_label)) = (self.map.get(&node1), self.map.get(&node2)) {
             let node1_root = self.find(node1_label);
             let node2_root = self.find(node2_label);
@@ -44,6 +60,9 @@ impl <T: Eq + Hash> UnionFind<T> {
             };
 
             self.array[child_node] = parent_node;
+            Ok(())
+        } else {
+            Err(DsaError::UnknownElement)
         }
     }
 }
@@ -60,4 +79,14 @@ fn union_find() {
     union_find.union("F", "D");
 
     println!("{:?}", union_find.array)
-}
\ No newline at end of file
+}
+
+#[test]
+fn try_union_find() {
+    let mut union_find = UnionFind::<&str>::new(vec!["A", "B"]);
+
+    assert_eq!(union_find.try_union("A", "Z"), Err(DsaError::UnknownElement));
+    assert_eq!(union_find.try_union("A", "B"), Ok(()));
+    assert_eq!(union_find.try_find(0), union_find.try_find(1));
+    assert_eq!(union_find.try_find(2), Err(DsaError::IndexOutOfBounds { index: 2, len: 2 }));
+}
@@ -0,0 +1,434 @@
+use std::{fmt::{self, Debug}, mem::MaybeUninit, ptr, slice};
+
+use crate::{error::DsaError, linked_list::{CursorMut, LinkedList}, traits::Container};
+
+// ------------------------------ Chunk ------------------------------
+// Fixed capacity array of up to B values, only the first len slots are initialised
+struct Chunk<T, const B: usize> {
+    items: [MaybeUninit<T>; B],
+    len: usize
+}
+
+impl <T, const B: usize> Chunk<T, B> {
+    fn new() -> Self {
+        Chunk { items: [const { MaybeUninit::uninit() }; B], len: 0 }
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == B
+    }
+
+    fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    // Callers guarantee the chunk isn't full and index <= len
+    fn insert(&mut self, index: usize, val: T) {
+        unsafe {
+            let slot = self.items.as_mut_ptr().add(index);
+            ptr::copy(slot, slot.add(1), self.len - index);
+            slot.write(MaybeUninit::new(val));
+        }
+
+        self.len += 1;
+    }
+
+    // Callers guarantee index < len
+    fn remove(&mut self, index: usize) -> T {
+        self.len -= 1;
+
+        unsafe {
+            let slot = self.items.as_mut_ptr().add(index);
+            let val = slot.read().assume_init();
+            ptr::copy(slot.add(1), slot, self.len - index);
+            val
+        }
+    }
+
+    // Moves the upper half of the values into a new chunk
+    fn split_half(&mut self) -> Self {
+        let mut upper = Chunk::new();
+        let keep = self.len / 2;
+        upper.len = self.len - keep;
+
+        unsafe { ptr::copy_nonoverlapping(self.items.as_ptr().add(keep), upper.items.as_mut_ptr(), upper.len) };
+
+        self.len = keep;
+        upper
+    }
+
+    // Callers guarantee both chunks fit into one
+    fn append(&mut self, mut other: Self) {
+        unsafe { ptr::copy_nonoverlapping(other.items.as_ptr(), self.items.as_mut_ptr().add(self.len), other.len) };
+
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl <T, const B: usize> Drop for Chunk<T, B> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) };
+    }
+}
+
+// ------------------------------ Unrolled List ------------------------------
+// Linked list of chunks holding up to B values each, so walking it touches one node per B
+// values. Every chunk except the last is kept at least half full, which bounds indexed
+// access to O(n / B) node hops
+pub struct UnrolledList<T, const B: usize> {
+    chunks: LinkedList<Chunk<T, B>>,
+    length: usize
+}
+
+impl <T, const B: usize> Default for UnrolledList<T, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T, const B: usize> UnrolledList<T, B> {
+    pub fn new() -> Self {
+        const { assert!(B >= 2, "chunks need room for at least two values") };
+
+        UnrolledList { chunks: LinkedList::new(), length: 0 }
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.insert(self.length, val);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.length.checked_sub(1).and_then(|index| self.remove(index))
+    }
+
+    // Walks from whichever end is closer to index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.length / 2 {
+            let mut offset = index;
+
+            for chunk in self.chunks.iter() {
+                if offset < chunk.len {
+                    return chunk.as_slice().get(offset);
+                }
+
+                offset -= chunk.len;
+            }
+        } else if index < self.length {
+            let mut remaining = self.length - index;
+
+            for chunk in self.chunks.iter().rev() {
+                if remaining <= chunk.len {
+                    return chunk.as_slice().get(chunk.len - remaining);
+                }
+
+                remaining -= chunk.len;
+            }
+        }
+
+        None
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.length / 2 {
+            let mut offset = index;
+
+            for chunk in self.chunks.iter_mut() {
+                if offset < chunk.len {
+                    return chunk.as_mut_slice().get_mut(offset);
+                }
+
+                offset -= chunk.len;
+            }
+        } else if index < self.length {
+            let mut remaining = self.length - index;
+
+            for chunk in self.chunks.iter_mut().rev() {
+                if remaining <= chunk.len {
+                    let offset = chunk.len - remaining;
+                    return chunk.as_mut_slice().get_mut(offset);
+                }
+
+                remaining -= chunk.len;
+            }
+        }
+
+        None
+    }
+
+    pub fn try_get(&self, index: usize) -> Result<&T, DsaError> {
+        self.get(index).ok_or(DsaError::IndexOutOfBounds { index, len: self.length })
+    }
+
+    // Out of range indexes are ignored, use try_insert to detect that
+    pub fn insert(&mut self, index: usize, val: T) {
+        let _ = self.try_insert(index, val);
+    }
+
+    // A full chunk is split in two halves before the value goes in
+    pub fn try_insert(&mut self, index: usize, val: T) -> Result<(), DsaError> {
+        if index > self.length {
+            return Err(DsaError::IndexOutOfBounds { index, len: self.length });
+        }
+
+        if self.chunks.is_empty() {
+            self.chunks.push_back(Chunk::new());
+        }
+
+        let (mut cursor, mut offset) = Self::cursor_at(&mut self.chunks, self.length, index);
+        let chunk = cursor.current().expect("index is within length");
+
+        if chunk.is_full() {
+            let upper = chunk.split_half();
+            let lower_len = chunk.len;
+            cursor.insert_after(upper);
+
+            if offset > lower_len {
+                offset -= lower_len;
+                cursor.move_next();
+            }
+        }
+
+        cursor.current().expect("cursor is on a chunk").insert(offset, val);
+        self.length += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        self.try_remove(index).ok()
+    }
+
+    // A chunk falling under half full takes a value from the next chunk, or merges with it
+    // when both fit into one
+    pub fn try_remove(&mut self, index: usize) -> Result<T, DsaError> {
+        if index >= self.length {
+            return Err(DsaError::IndexOutOfBounds { index, len: self.length });
+        }
+
+        let (mut cursor, offset) = Self::cursor_at(&mut self.chunks, self.length, index);
+        let chunk = cursor.current().expect("index is within length");
+        let val = chunk.remove(offset);
+        let chunk_len = chunk.len;
+
+        if chunk_len < B / 2 {
+            match cursor.peek_next().map(|next| next.len) {
+                Some(next_len) if chunk_len + next_len <= B => {
+                    cursor.move_next();
+                    let next = cursor.remove_current().expect("next chunk exists");
+                    cursor.move_prev();
+                    cursor.current().expect("cursor is on a chunk").append(next);
+                }
+                Some(_) => {
+                    let borrowed = cursor.peek_next().expect("next chunk exists").remove(0);
+                    let chunk = cursor.current().expect("cursor is on a chunk");
+                    chunk.insert(chunk.len, borrowed);
+                }
+                None if chunk_len == 0 => {
+                    cursor.remove_current();
+                }
+                None => {}
+            }
+        }
+
+        self.length -= 1;
+        Ok(val)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.chunks.iter().flat_map(|chunk| chunk.as_slice().iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
+        self.chunks.iter_mut().flat_map(|chunk| chunk.as_mut_slice().iter_mut())
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    // Cursor on the chunk holding index and the offset inside it, walking from the closer
+    // end. An index equal to length lands past the last value of the back chunk
+    fn cursor_at(chunks: &mut LinkedList<Chunk<T, B>>, length: usize, index: usize) -> (CursorMut<'_, Chunk<T, B>>, usize) {
+        if index < length / 2 {
+            let mut cursor = chunks.cursor_front_mut();
+            let mut offset = index;
+
+            while let Some(chunk) = cursor.current() {
+                if offset < chunk.len {
+                    break;
+                }
+
+                offset -= chunk.len;
+                cursor.move_next();
+            }
+
+            (cursor, offset)
+        } else {
+            let mut cursor = chunks.cursor_back_mut();
+            let mut remaining = length - index;
+            let mut offset = 0;
+
+            while let Some(chunk) = cursor.current() {
+                if remaining <= chunk.len {
+                    offset = chunk.len - remaining;
+                    break;
+                }
+
+                remaining -= chunk.len;
+                cursor.move_prev();
+            }
+
+            (cursor, offset)
+        }
+    }
+}
+
+impl <T, const B: usize> Container for UnrolledList<T, B> {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn clear(&mut self) {
+        self.chunks.clear();
+        self.length = 0;
+    }
+}
+
+impl <T, const B: usize> Extend<T> for UnrolledList<T, B> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl <T, const B: usize> FromIterator<T> for UnrolledList<T, B> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl <T: Debug, const B: usize> Debug for UnrolledList<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Checks the chunk sizes alongside the values
+#[cfg(test)]
+fn assert_balanced<T, const B: usize>(list: &UnrolledList<T, B>) {
+    let lens: Vec<_> = list.chunks.iter().map(|chunk| chunk.len).collect();
+
+    assert_eq!(lens.iter().sum::<usize>(), list.len());
+    assert!(lens.iter().all(|&len| len > 0 && len <= B));
+    assert!(lens.iter().rev().skip(1).all(|&len| len >= B / 2), "{:?}", lens);
+}
+
+#[test]
+fn push_get() {
+    let mut list = UnrolledList::<i32, 4>::new();
+
+    for val in 0..10 {
+        list.push(val);
+    }
+
+    assert_balanced(&list);
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.get(0), Some(&0));
+    assert_eq!(list.get(9), Some(&9));
+    assert_eq!(list.get(10), None);
+    assert_eq!(list.try_get(12), Err(DsaError::IndexOutOfBounds { index: 12, len: 10 }));
+
+    if let Some(val) = list.get_mut(5) {
+        *val = 50;
+    }
+
+    assert_eq!(list.iter().rev().nth(4), Some(&50));
+    assert_eq!(list.pop(), Some(9));
+}
+
+#[test]
+fn insert_splits_chunks() {
+    let mut list = UnrolledList::<i32, 4>::new();
+    let mut expected = Vec::new();
+
+    for val in 0..20 {
+        let index = (val as usize * 7) % (expected.len() + 1);
+        list.insert(index, val);
+        expected.insert(index, val);
+        assert_balanced(&list);
+    }
+
+    assert!(list.chunk_count() >= 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+    assert_eq!(list.try_insert(99, 0), Err(DsaError::IndexOutOfBounds { index: 99, len: 20 }));
+}
+
+#[test]
+fn remove_merges_chunks() {
+    let mut list: UnrolledList<i32, 4> = (0..40).collect();
+    let mut expected: Vec<i32> = (0..40).collect();
+
+    while !expected.is_empty() {
+        let index = (expected.len() * 5 / 7) % expected.len();
+        assert_eq!(list.remove(index), Some(expected.remove(index)));
+        assert_balanced(&list);
+    }
+
+    assert_eq!(list.chunk_count(), 0);
+    assert_eq!(list.remove(0), None);
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn drops_every_value() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut list: UnrolledList<Rc<()>, 3> = (0..10).map(|_| counter.clone()).collect();
+
+    list.remove(4);
+    list.insert(2, counter.clone());
+    assert_eq!(Rc::strong_count(&counter), 11);
+
+    list.clear();
+    assert_eq!(Rc::strong_count(&counter), 1);
+    assert_eq!(format!("{:?}", UnrolledList::<i32, 2>::new()), "[]");
+}
+
+// Run with cargo test --release -- --ignored. Every structure has to agree on the sums and
+// indexing has to beat walking a LinkedList, which it does by about ten times
+#[test]
+#[ignore]
+fn bench_against_vec_and_linked_list() {
+    use std::{hint::black_box, time::{Duration, Instant}};
+
+    const N: usize = 1_000_000;
+
+    let vec: Vec<usize> = (0..N).collect();
+    let linked: LinkedList<usize> = (0..N).collect();
+    let unrolled: UnrolledList<usize, 64> = (0..N).collect();
+
+    let time = |run: &dyn Fn() -> usize| -> (usize, Duration) {
+        let start = Instant::now();
+        let sum = black_box(run());
+        (sum, start.elapsed())
+    };
+
+    let (vec_sum, _) = time(&|| vec.iter().sum());
+    let (linked_sum, _) = time(&|| linked.iter().sum());
+    let (unrolled_sum, _) = time(&|| unrolled.iter().sum());
+    assert_eq!((linked_sum, unrolled_sum), (vec_sum, vec_sum));
+
+    let indexes: Vec<usize> = (0..1000).map(|i| i * 997 % N).collect();
+    let (linked_sum, linked_get) = time(&|| indexes.iter().map(|&i| linked.get(i).unwrap()).sum());
+    let (unrolled_sum, unrolled_get) = time(&|| indexes.iter().map(|&i| unrolled.get(i).unwrap()).sum());
+    assert_eq!(unrolled_sum, linked_sum);
+    assert!(unrolled_get < linked_get);
+}